@@ -0,0 +1,52 @@
+//! Cost of the fill primitives for growing shapes on a small and a large screen.
+//! The time per shape should follow the shape's area, not the screen's.
+//!
+//! Run with `cargo bench --bench shapes`.
+
+use std::time::{Duration, Instant};
+use teremder::*;
+
+const ITERATIONS: u32 = 200;
+
+fn bench<F>(ctx: &mut Context, mut f: F) -> Duration
+where
+    F: FnMut(&mut Context),
+{
+    let start = Instant::now();
+    for _ in 0..ITERATIONS {
+        f(ctx);
+    }
+    start.elapsed() / ITERATIONS
+}
+
+fn main() {
+    let screens = [(80, 24), (400, 120)];
+    let sizes = [2., 8., 32.];
+
+    for (width, height) in screens {
+        let mut ctx = Context::headless(width, height);
+        println!(
+            "screen {}x{} pixels",
+            ctx.screen_width(),
+            ctx.screen_height()
+        );
+        for size in sizes {
+            let circle = bench(&mut ctx, |ctx| ctx.fill_circle(40., 24., size, RED));
+            let triangle = bench(&mut ctx, |ctx| {
+                ctx.fill_triangle(
+                    40.,
+                    24. - size,
+                    40. + size,
+                    24. + size,
+                    40. - size,
+                    24. + size,
+                    RED,
+                )
+            });
+            let rect = bench(&mut ctx, |ctx| ctx.fill_rect(40., 24., size, size, RED));
+            println!(
+                "  size {size:>4}: fill_circle {circle:>10.2?}  fill_triangle {triangle:>10.2?}  fill_rect {rect:>10.2?}"
+            );
+        }
+    }
+}
@@ -1,4 +1,7 @@
-use crate::{Color, Context, Key, MouseButton};
+use crate::{
+    BlendMode, Camera2D, Color, Context, FillRule, Key, LayerStyle, MouseButton, Paint, Path,
+    Rectangle, ScaleMode, Sprite, Vec2, Viewport, Window,
+};
 
 static mut CONTEXT: Option<Context> = None;
 pub fn ctx() -> &'static mut Context {
@@ -12,6 +15,14 @@ pub fn ctx() -> &'static mut Context {
     }
 }
 
+/// Replaces the global context, e.g. with a [`Context::headless`] one in tests.
+pub fn set_context(context: Context) {
+    #![allow(static_mut_refs)]
+    unsafe {
+        let _ = CONTEXT.insert(context);
+    }
+}
+
 pub fn setup_panic_hook() {
     let default_hook = std::panic::take_hook();
     std::panic::set_hook(Box::new(move |info| {
@@ -28,8 +39,11 @@ pub fn next_frame() {
     ctx().next_frame();
 }
 
-pub fn fill_rect(x: f32, y: f32, width: f32, height: f32, color: Color) {
-    ctx().fill_rect(x, y, width, height, color);
+pub fn fill_rect<P>(x: f32, y: f32, width: f32, height: f32, paint: P)
+where
+    P: Into<Paint>,
+{
+    ctx().fill_rect(x, y, width, height, paint);
 }
 
 pub fn screen_width() -> f32 {
@@ -44,8 +58,11 @@ pub fn mouse_position() -> (f32, f32) {
     ctx().mouse_position()
 }
 
-pub fn clear_background(color: Color) {
-    ctx().clear_background(color);
+pub fn clear_background<P>(paint: P)
+where
+    P: Into<Paint>,
+{
+    ctx().clear_background(paint);
 }
 
 pub fn exit_app(panic: bool) {
@@ -87,6 +104,10 @@ pub fn set_target_fps(fps: f32) {
     ctx().set_target_fps(fps);
 }
 
+pub fn get_frame_time() -> f32 {
+    ctx().get_frame_time()
+}
+
 pub fn is_key_down(key: Key) -> bool {
     ctx().is_key_down(key)
 }
@@ -120,10 +141,269 @@ where
     ctx().rng(min, max)
 }
 
-pub fn fill_circle(x: f32, y: f32, radius: f32, color: Color) {
-    ctx().fill_circle(x, y, radius, color);
+pub fn fill_circle<P>(x: f32, y: f32, radius: f32, paint: P)
+where
+    P: Into<Paint>,
+{
+    ctx().fill_circle(x, y, radius, paint);
+}
+
+#[allow(clippy::too_many_arguments)]
+pub fn fill_triangle<P>(x1: f32, y1: f32, x2: f32, y2: f32, x3: f32, y3: f32, paint: P)
+where
+    P: Into<Paint>,
+{
+    ctx().fill_triangle(x1, y1, x2, y2, x3, y3, paint);
+}
+
+pub fn draw_line(x1: f32, y1: f32, x2: f32, y2: f32, color: Color) {
+    ctx().draw_line(x1, y1, x2, y2, color);
+}
+
+pub fn draw_line_thick(x1: f32, y1: f32, x2: f32, y2: f32, thickness: f32, color: Color) {
+    ctx().draw_line_thick(x1, y1, x2, y2, thickness, color);
+}
+
+pub fn draw_line_aa(x1: f32, y1: f32, x2: f32, y2: f32, color: Color) {
+    ctx().draw_line_aa(x1, y1, x2, y2, color);
+}
+
+pub fn draw_rect(x: f32, y: f32, width: f32, height: f32, thickness: f32, color: Color) {
+    ctx().draw_rect(x, y, width, height, thickness, color);
+}
+
+pub fn draw_circle(x: f32, y: f32, radius: f32, thickness: f32, color: Color) {
+    ctx().draw_circle(x, y, radius, thickness, color);
+}
+
+pub fn draw_ellipse(x: f32, y: f32, radius_x: f32, radius_y: f32, thickness: f32, color: Color) {
+    ctx().draw_ellipse(x, y, radius_x, radius_y, thickness, color);
+}
+
+#[allow(clippy::too_many_arguments)]
+pub fn draw_triangle(
+    x1: f32,
+    y1: f32,
+    x2: f32,
+    y2: f32,
+    x3: f32,
+    y3: f32,
+    thickness: f32,
+    color: Color,
+) {
+    ctx().draw_triangle(x1, y1, x2, y2, x3, y3, thickness, color);
+}
+
+pub fn draw_polygon(points: &[Vec2], thickness: f32, color: Color) {
+    ctx().draw_polygon(points, thickness, color);
+}
+
+pub fn fill_ellipse<P>(x: f32, y: f32, radius_x: f32, radius_y: f32, paint: P)
+where
+    P: Into<Paint>,
+{
+    ctx().fill_ellipse(x, y, radius_x, radius_y, paint);
+}
+
+#[allow(clippy::too_many_arguments)]
+pub fn draw_arc(
+    x: f32,
+    y: f32,
+    radius: f32,
+    start_angle: f32,
+    end_angle: f32,
+    thickness: f32,
+    color: Color,
+) {
+    ctx().draw_arc(x, y, radius, start_angle, end_angle, thickness, color);
+}
+
+pub fn fill_pie<P>(x: f32, y: f32, radius: f32, start_angle: f32, end_angle: f32, paint: P)
+where
+    P: Into<Paint>,
+{
+    ctx().fill_pie(x, y, radius, start_angle, end_angle, paint);
+}
+
+pub fn fill_rounded_rect<P>(x: f32, y: f32, width: f32, height: f32, radius: f32, paint: P)
+where
+    P: Into<Paint>,
+{
+    ctx().fill_rounded_rect(x, y, width, height, radius, paint);
+}
+
+#[allow(clippy::too_many_arguments)]
+pub fn draw_rounded_rect(
+    x: f32,
+    y: f32,
+    width: f32,
+    height: f32,
+    radius: f32,
+    thickness: f32,
+    color: Color,
+) {
+    ctx().draw_rounded_rect(x, y, width, height, radius, thickness, color);
+}
+
+pub fn fill_polygon<P>(points: &[Vec2], paint: P)
+where
+    P: Into<Paint>,
+{
+    ctx().fill_polygon(points, paint);
+}
+
+pub fn fill_polygon_with_rule<P>(points: &[Vec2], rule: FillRule, paint: P)
+where
+    P: Into<Paint>,
+{
+    ctx().fill_polygon_with_rule(points, rule, paint);
+}
+
+pub fn fill_path<P>(path: &Path, paint: P)
+where
+    P: Into<Paint>,
+{
+    ctx().fill_path(path, paint);
+}
+
+pub fn stroke_path(path: &Path, thickness: f32, color: Color) {
+    ctx().stroke_path(path, thickness, color);
 }
 
-pub fn fill_triangle(x1: f32, y1: f32, x2: f32, y2: f32, x3: f32, y3: f32, color: Color) {
-    ctx().fill_triangle(x1, y1, x2, y2, x3, y3, color);
+pub fn draw_bezier_quad(p0: Vec2, p1: Vec2, p2: Vec2, thickness: f32, color: Color) {
+    ctx().draw_bezier_quad(p0, p1, p2, thickness, color);
+}
+
+pub fn draw_bezier_cubic(p0: Vec2, p1: Vec2, p2: Vec2, p3: Vec2, thickness: f32, color: Color) {
+    ctx().draw_bezier_cubic(p0, p1, p2, p3, thickness, color);
+}
+
+pub fn set_blend_mode(mode: BlendMode) {
+    ctx().set_blend_mode(mode);
+}
+
+pub fn with_blend_mode<F, R>(mode: BlendMode, f: F) -> R
+where
+    F: FnOnce() -> R,
+{
+    let previous = ctx().blend_mode();
+    ctx().set_blend_mode(mode);
+    let result = f();
+    ctx().set_blend_mode(previous);
+    result
+}
+
+pub fn push_clip(rect: Rectangle) {
+    ctx().push_clip(rect);
+}
+
+pub fn pop_clip() -> Option<Rectangle> {
+    ctx().pop_clip()
+}
+
+pub fn with_clip<F, R>(rect: Rectangle, f: F) -> R
+where
+    F: FnOnce() -> R,
+{
+    ctx().push_clip(rect);
+    let result = f();
+    ctx().pop_clip();
+    result
+}
+
+pub fn set_camera(camera: Camera2D) {
+    ctx().set_camera(camera);
+}
+
+pub fn set_default_camera() {
+    ctx().set_default_camera();
+}
+
+pub fn world_to_screen(point: Vec2) -> Vec2 {
+    ctx().world_to_screen(point)
+}
+
+pub fn screen_to_world(point: Vec2) -> Vec2 {
+    ctx().screen_to_world(point)
+}
+
+pub fn mouse_world_position() -> (f32, f32) {
+    ctx().mouse_world_position()
+}
+
+pub fn set_virtual_resolution(width: f32, height: f32, mode: ScaleMode) {
+    ctx().set_virtual_resolution(width, height, mode);
+}
+
+pub fn clear_virtual_resolution() {
+    ctx().clear_virtual_resolution();
+}
+
+pub fn draw_to_sprite<F, R>(sprite: &mut Sprite, f: F) -> R
+where
+    F: FnOnce() -> R,
+{
+    ctx().begin_sprite_target(sprite);
+    let result = f();
+    ctx().end_sprite_target(sprite);
+    result
+}
+
+pub fn set_layer(index: u32) {
+    ctx().set_layer(index);
+}
+
+pub fn with_layer<F, R>(index: u32, f: F) -> R
+where
+    F: FnOnce() -> R,
+{
+    let previous = ctx().layer();
+    ctx().set_layer(index);
+    let result = f();
+    ctx().set_layer(previous);
+    result
+}
+
+pub fn set_layer_style(index: u32, style: LayerStyle) {
+    ctx().set_layer_style(index, style);
+}
+
+pub fn set_layer_opacity(index: u32, opacity: f32) {
+    ctx().set_layer_opacity(index, opacity);
+}
+
+pub fn set_layer_visible(index: u32, visible: bool) {
+    ctx().set_layer_visible(index, visible);
+}
+
+pub fn set_layer_blend_mode(index: u32, mode: BlendMode) {
+    ctx().set_layer_blend_mode(index, mode);
+}
+
+pub fn draw_window<F, R>(window: &Window, f: F) -> Option<R>
+where
+    F: FnOnce() -> R,
+{
+    if !ctx().begin_window(window) {
+        return None;
+    }
+    let result = f();
+    ctx().end_window();
+    Some(result)
+}
+
+pub fn draw_viewport<F, R>(viewport: &Viewport, f: F) -> R
+where
+    F: FnOnce() -> R,
+{
+    draw_window(&viewport.window(), f).unwrap()
+}
+
+pub fn draw_viewports<F>(viewports: &[Viewport], mut f: F)
+where
+    F: FnMut(usize),
+{
+    for (i, viewport) in viewports.iter().enumerate() {
+        draw_viewport(viewport, || f(i));
+    }
 }
@@ -0,0 +1,139 @@
+use crate::{Pixel, Vec2d};
+use crossterm::{
+    cursor,
+    event::{self, Event, KeyboardEnhancementFlags, PushKeyboardEnhancementFlags},
+    execute, queue, style, terminal,
+};
+use std::io::{self, Stdout, Write};
+use std::time::Duration;
+
+/// Where the [`Context`](crate::Context) sends its frames and gets its input from.
+pub trait Backend {
+    /// Called once when the context is created.
+    fn init(&mut self) -> io::Result<()>;
+
+    /// Size of the output in cells, every cell holds two vertically stacked pixels.
+    fn size(&self) -> io::Result<(u16, u16)>;
+
+    /// Draws every cell of `current` that differs from `previous`.
+    fn present(&mut self, previous: &Vec2d<Pixel>, current: &Vec2d<Pixel>) -> io::Result<()>;
+
+    /// Returns the next pending event without blocking.
+    fn poll_event(&mut self) -> io::Result<Option<Event>>;
+
+    /// Undoes whatever `init` did, called when the context is dropped.
+    fn restore(&mut self) -> io::Result<()>;
+}
+
+pub struct CrosstermBackend {
+    stdout: Stdout,
+}
+
+impl Default for CrosstermBackend {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl CrosstermBackend {
+    pub fn new() -> Self {
+        Self {
+            stdout: io::stdout(),
+        }
+    }
+}
+
+impl Backend for CrosstermBackend {
+    fn init(&mut self) -> io::Result<()> {
+        terminal::enable_raw_mode()?;
+        execute!(
+            self.stdout,
+            cursor::Hide,
+            terminal::EnterAlternateScreen,
+            PushKeyboardEnhancementFlags(KeyboardEnhancementFlags::all()),
+            event::EnableMouseCapture,
+        )
+    }
+
+    fn size(&self) -> io::Result<(u16, u16)> {
+        terminal::size()
+    }
+
+    fn present(&mut self, previous: &Vec2d<Pixel>, current: &Vec2d<Pixel>) -> io::Result<()> {
+        // queue!(self.stdout, terminal::BeginSynchronizedUpdate)?;
+        for y in 0..current.height() / 2 {
+            for x in 0..current.width() {
+                let fg = current.get(x, y * 2);
+                let bg = current.get(x, y * 2 + 1);
+                if fg == previous.get(x, y * 2) && bg == previous.get(x, y * 2 + 1) {
+                    continue;
+                }
+                queue!(
+                    self.stdout,
+                    cursor::MoveTo(x as u16, y as u16),
+                    style::SetForegroundColor(fg.into()),
+                    style::SetBackgroundColor(bg.into()),
+                    style::Print('▀'),
+                )?;
+            }
+        }
+        // queue!(self.stdout, terminal::EndSynchronizedUpdate)?;
+        self.stdout.flush()
+    }
+
+    fn poll_event(&mut self) -> io::Result<Option<Event>> {
+        if event::poll(Duration::from_millis(0))? {
+            event::read().map(Some)
+        } else {
+            Ok(None)
+        }
+    }
+
+    fn restore(&mut self) -> io::Result<()> {
+        execute!(
+            self.stdout,
+            cursor::Show,
+            style::ResetColor,
+            terminal::LeaveAlternateScreen,
+            event::PopKeyboardEnhancementFlags,
+            event::DisableMouseCapture,
+        )?;
+        terminal::disable_raw_mode()
+    }
+}
+
+/// Backend that doesn't draw anywhere, for running a context without a terminal.
+#[derive(Debug, Clone, Copy)]
+pub struct HeadlessBackend {
+    width: u16,
+    height: u16,
+}
+
+impl HeadlessBackend {
+    /// `width` and `height` are in cells, like [`Backend::size`].
+    pub fn new(width: u16, height: u16) -> Self {
+        Self { width, height }
+    }
+}
+
+impl Backend for HeadlessBackend {
+    fn init(&mut self) -> io::Result<()> {
+        Ok(())
+    }
+
+    fn size(&self) -> io::Result<(u16, u16)> {
+        Ok((self.width, self.height))
+    }
+
+    fn present(&mut self, _previous: &Vec2d<Pixel>, _current: &Vec2d<Pixel>) -> io::Result<()> {
+        Ok(())
+    }
+
+    fn poll_event(&mut self) -> io::Result<Option<Event>> {
+        Ok(None)
+    }
+
+    fn restore(&mut self) -> io::Result<()> {
+        Ok(())
+    }
+}
@@ -0,0 +1,92 @@
+use crate::{Color, Context, Pixel};
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub enum BlendMode {
+    /// Regular source-over compositing.
+    #[default]
+    Alpha,
+    Additive,
+    Multiply,
+    Screen,
+    Subtract,
+    /// Writes the color as is, ignoring alpha.
+    Replace,
+}
+
+impl BlendMode {
+    pub fn blend(&self, dst: Pixel, src: Color) -> Pixel {
+        let op: fn(u32, u32) -> u32 = match self {
+            BlendMode::Alpha => return dst.blend(src),
+            BlendMode::Replace => {
+                return Pixel {
+                    r: src.r,
+                    g: src.g,
+                    b: src.b,
+                }
+            }
+            BlendMode::Additive => |s, d| (s + d).min(255),
+            BlendMode::Multiply => |s, d| (s * d + 127) / 255,
+            BlendMode::Screen => |s, d| 255 - ((255 - s) * (255 - d) + 127) / 255,
+            BlendMode::Subtract => |s, d| d.saturating_sub(s),
+        };
+        let channel = |s: u8, d: u8| op(s as u32, d as u32) as u8;
+        dst.blend(Color::rgba(
+            channel(src.r, dst.r),
+            channel(src.g, dst.g),
+            channel(src.b, dst.b),
+            src.a,
+        ))
+    }
+
+    /// Like [`BlendMode::blend`], but onto a color that can itself be transparent.
+    /// The mode only applies as much as `dst` is opaque, where it's transparent `src` is kept as is.
+    pub fn blend_color(&self, dst: Color, src: Color) -> Color {
+        if *self == BlendMode::Replace || dst.a == 0 {
+            return src;
+        }
+        let mixed = self.blend(
+            Pixel {
+                r: dst.r,
+                g: dst.g,
+                b: dst.b,
+            },
+            Color::rgba(src.r, src.g, src.b, 255),
+        );
+        let (sa, da) = (src.a as u32, dst.a as u32);
+        let out_a = sa * 255 + da * (255 - sa);
+        if out_a == 0 {
+            return Color::rgba(0, 0, 0, 0);
+        }
+        let channel = |s: u8, m: u8, d: u8| {
+            let s = (s as u32 * (255 - da) + m as u32 * da) / 255;
+            ((s * sa * 255 + d as u32 * da * (255 - sa) + out_a / 2) / out_a) as u8
+        };
+        Color::rgba(
+            channel(src.r, mixed.r, dst.r),
+            channel(src.g, mixed.g, dst.g),
+            channel(src.b, mixed.b, dst.b),
+            ((out_a + 127) / 255) as u8,
+        )
+    }
+}
+
+impl Context {
+    pub fn set_blend_mode(&mut self, mode: BlendMode) {
+        self.blend_mode = mode;
+    }
+
+    pub fn blend_mode(&self) -> BlendMode {
+        self.blend_mode
+    }
+
+    /// Draws everything in `f` with `mode`, then goes back to the previous mode.
+    pub fn with_blend_mode<F, R>(&mut self, mode: BlendMode, f: F) -> R
+    where
+        F: FnOnce(&mut Self) -> R,
+    {
+        let previous = std::mem::replace(&mut self.blend_mode, mode);
+        let result = f(self);
+        self.blend_mode = previous;
+        result
+    }
+}
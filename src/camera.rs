@@ -0,0 +1,103 @@
+use crate::{Context, Transform, Vec2};
+
+/// Looks at `target` in the world and puts it at `offset` on the screen.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct Camera2D {
+    pub target: Vec2,
+    pub offset: Vec2,
+    pub zoom: f32,
+    /// In radians, clockwise.
+    pub rotation: f32,
+}
+
+impl Default for Camera2D {
+    fn default() -> Self {
+        Self {
+            target: Vec2::ZERO,
+            offset: Vec2::ZERO,
+            zoom: 1.,
+            rotation: 0.,
+        }
+    }
+}
+
+impl Camera2D {
+    pub fn new(target: Vec2, offset: Vec2) -> Self {
+        Self {
+            target,
+            offset,
+            ..Default::default()
+        }
+    }
+
+    pub fn transform(&self) -> Transform {
+        let around_target = Transform::new(Vec2::ZERO, self.rotation, self.zoom);
+        Transform::translation(-self.target)
+            .then(&around_target)
+            .then(&Transform::translation(self.offset))
+    }
+
+    pub fn world_to_screen(&self, point: Vec2) -> Vec2 {
+        self.transform().apply(point)
+    }
+
+    pub fn screen_to_world(&self, point: Vec2) -> Vec2 {
+        self.transform().inverse().apply(point)
+    }
+}
+
+impl Context {
+    /// Everything drawn from now on is in world space, seen through `camera`.
+    /// Clipping rectangles stay in screen space.
+    pub fn set_camera(&mut self, camera: Camera2D) {
+        self.camera = Some(camera);
+    }
+
+    /// Goes back to drawing in screen space.
+    pub fn set_default_camera(&mut self) {
+        self.camera = None;
+    }
+
+    pub fn camera(&self) -> Option<&Camera2D> {
+        self.camera.as_ref()
+    }
+
+    pub fn world_to_screen(&self, point: Vec2) -> Vec2 {
+        self.camera
+            .map_or(point, |camera| camera.world_to_screen(point))
+    }
+
+    pub fn screen_to_world(&self, point: Vec2) -> Vec2 {
+        self.camera
+            .map_or(point, |camera| camera.screen_to_world(point))
+    }
+
+    /// The mouse position seen through the current camera.
+    pub fn mouse_world_position(&self) -> (f32, f32) {
+        self.screen_to_world(self.mouse_position().into()).into()
+    }
+
+    /// What world space drawing goes through to reach the screen (camera, then window origin),
+    /// `None` if nothing.
+    pub(crate) fn transform(&self) -> Option<Transform> {
+        let camera = self
+            .camera
+            .map_or(Transform::IDENTITY, |camera| camera.transform());
+        Some(camera.then(&Transform::translation(self.origin))).filter(|t| !t.is_identity())
+    }
+
+    /// Runs `f` drawing straight in screen space, outside of any window.
+    pub(crate) fn untransformed<F, R>(&mut self, f: F) -> R
+    where
+        F: FnOnce(&mut Self) -> R,
+    {
+        let camera = self.camera.take();
+        let origin = std::mem::take(&mut self.origin);
+        let size = self.view_size.take();
+        let result = f(self);
+        self.camera = camera;
+        self.origin = origin;
+        self.view_size = size;
+        result
+    }
+}
@@ -0,0 +1,50 @@
+use crate::{Context, Rectangle};
+
+impl Context {
+    /// Restricts drawing to `rect` (in screen pixels, relative to the current window)
+    /// until the matching [`Context::pop_clip`].
+    /// Nested clips only leave the area shared with the ones below them.
+    pub fn push_clip(&mut self, rect: Rectangle) {
+        let rect = Rectangle::new(
+            (rect.x + self.origin.x).floor(),
+            (rect.y + self.origin.y).floor(),
+            rect.w.trunc(),
+            rect.h.trunc(),
+        );
+        let clip = match self.clip_stack.last() {
+            Some(top) => top.intersection(&rect).unwrap_or_default(),
+            None => rect,
+        };
+        self.clip_stack.push(clip);
+    }
+
+    pub fn pop_clip(&mut self) -> Option<Rectangle> {
+        self.clip_stack.pop()
+    }
+
+    /// Draws everything in `f` clipped to `rect`.
+    pub fn with_clip<F, R>(&mut self, rect: Rectangle, f: F) -> R
+    where
+        F: FnOnce(&mut Self) -> R,
+    {
+        self.push_clip(rect);
+        let result = f(self);
+        self.pop_clip();
+        result
+    }
+
+    /// The area that can currently be drawn to, as `(x_start, y_start, x_end, y_end)`.
+    pub(crate) fn clip_bounds(&self) -> (i32, i32, i32, i32) {
+        let canvas = self.canvas();
+        let (width, height) = (canvas.width() as i32, canvas.height() as i32);
+        match self.clip_stack.last() {
+            Some(clip) => (
+                (clip.x as i32).clamp(0, width),
+                (clip.y as i32).clamp(0, height),
+                ((clip.x + clip.w) as i32).clamp(0, width),
+                ((clip.y + clip.h) as i32).clamp(0, height),
+            ),
+            None => (0, 0, width, height),
+        }
+    }
+}
@@ -1,6 +1,6 @@
 use crate::style;
 
-#[derive(Debug, Clone, Copy)]
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
 pub struct Color {
     pub r: u8,
     pub g: u8,
@@ -9,7 +9,7 @@ pub struct Color {
 }
 
 #[derive(Debug, Clone, Copy, Default, PartialEq)]
-pub(crate) struct Pixel {
+pub struct Pixel {
     pub r: u8,
     pub g: u8,
     pub b: u8,
@@ -25,6 +25,31 @@ impl From<&Pixel> for style::Color {
     }
 }
 
+impl Pixel {
+    /// Composites `color` over this pixel using its alpha.
+    pub fn blend(&self, color: Color) -> Self {
+        match color.a {
+            0 => *self,
+            255 => Self {
+                r: color.r,
+                g: color.g,
+                b: color.b,
+            },
+            a => {
+                let mix = |src: u8, dst: u8| {
+                    let (src, dst, a) = (src as u32, dst as u32, a as u32);
+                    ((src * a + dst * (255 - a) + 127) / 255) as u8
+                };
+                Self {
+                    r: mix(color.r, self.r),
+                    g: mix(color.g, self.g),
+                    b: mix(color.b, self.b),
+                }
+            }
+        }
+    }
+}
+
 impl Color {
     pub const fn rgba(r: u8, g: u8, b: u8, a: u8) -> Self {
         Self { r, g, b, a }
@@ -69,6 +94,21 @@ impl Color {
             a: (hex & 0xFF) as u8,
         }
     }
+
+    /// Parses `rrggbb` or `rrggbbaa` hex, optionally starting with `#`.
+    pub(crate) fn parse_hex(hex: &str) -> Option<Self> {
+        let hex = hex.strip_prefix('#').unwrap_or(hex);
+        // `from_str_radix` would also take a leading `+`
+        if !hex.chars().all(|c| c.is_ascii_hexdigit()) {
+            return None;
+        }
+        let n = u32::from_str_radix(hex, 16).ok()?;
+        match hex.len() {
+            6 => Some(Self::hex(n)),
+            8 => Some(Self::hex_a(n)),
+            _ => None,
+        }
+    }
 }
 
 macro_rules! def {
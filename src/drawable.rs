@@ -0,0 +1,138 @@
+use crate::{ctx, Circle, Color, Context, LineSegment, Paint, Path, Rectangle, Triangle, Vec2};
+
+/// Shapes that know how to draw themselves.
+///
+/// ```no_run
+/// # use teremder::*;
+/// rect!(2, 2, 10, 6).fill(RED).outline(WHITE, 1.).draw();
+/// ```
+pub trait Drawable {
+    fn fill_with_ctx(&self, ctx: &mut Context, paint: &Paint);
+
+    fn outline_with_ctx(&self, ctx: &mut Context, color: Color, thickness: f32);
+
+    fn fill<P>(&self, paint: P) -> ShapeStyle<'_, Self>
+    where
+        Self: Sized,
+        P: Into<Paint>,
+    {
+        ShapeStyle::new(self).fill(paint)
+    }
+
+    fn outline(&self, color: Color, thickness: f32) -> ShapeStyle<'_, Self>
+    where
+        Self: Sized,
+    {
+        ShapeStyle::new(self).outline(color, thickness)
+    }
+}
+
+/// A shape waiting to be drawn, the fill is drawn before the outline.
+#[must_use = "nothing is drawn until `draw` is called"]
+#[derive(Debug, Clone)]
+pub struct ShapeStyle<'a, S> {
+    shape: &'a S,
+    fill: Option<Paint>,
+    outline: Option<(Color, f32)>,
+}
+
+impl<'a, S: Drawable> ShapeStyle<'a, S> {
+    pub fn new(shape: &'a S) -> Self {
+        Self {
+            shape,
+            fill: None,
+            outline: None,
+        }
+    }
+
+    pub fn fill<P>(mut self, paint: P) -> Self
+    where
+        P: Into<Paint>,
+    {
+        self.fill = Some(paint.into());
+        self
+    }
+
+    pub fn outline(mut self, color: Color, thickness: f32) -> Self {
+        self.outline = Some((color, thickness));
+        self
+    }
+
+    pub fn draw(self) {
+        self.draw_with_ctx(ctx());
+    }
+
+    pub fn draw_with_ctx(self, ctx: &mut Context) {
+        if let Some(paint) = &self.fill {
+            self.shape.fill_with_ctx(ctx, paint);
+        }
+        if let Some((color, thickness)) = self.outline {
+            self.shape.outline_with_ctx(ctx, color, thickness);
+        }
+    }
+}
+
+impl Drawable for Rectangle {
+    fn fill_with_ctx(&self, ctx: &mut Context, paint: &Paint) {
+        ctx.fill_rect(self.x, self.y, self.w, self.h, paint.clone());
+    }
+
+    fn outline_with_ctx(&self, ctx: &mut Context, color: Color, thickness: f32) {
+        ctx.draw_rect(self.x, self.y, self.w, self.h, thickness, color);
+    }
+}
+
+impl Drawable for Circle {
+    fn fill_with_ctx(&self, ctx: &mut Context, paint: &Paint) {
+        ctx.fill_circle(self.center.x, self.center.y, self.radius, paint.clone());
+    }
+
+    fn outline_with_ctx(&self, ctx: &mut Context, color: Color, thickness: f32) {
+        ctx.draw_circle(self.center.x, self.center.y, self.radius, thickness, color);
+    }
+}
+
+impl Drawable for Triangle {
+    fn fill_with_ctx(&self, ctx: &mut Context, paint: &Paint) {
+        let Self { a, b, c } = self;
+        ctx.fill_triangle(a.x, a.y, b.x, b.y, c.x, c.y, paint.clone());
+    }
+
+    fn outline_with_ctx(&self, ctx: &mut Context, color: Color, thickness: f32) {
+        ctx.draw_polygon(&self.points(), thickness, color);
+    }
+}
+
+impl Drawable for LineSegment {
+    fn fill_with_ctx(&self, ctx: &mut Context, paint: &Paint) {
+        ctx.paint_line(self.start, self.end, paint);
+    }
+
+    fn outline_with_ctx(&self, ctx: &mut Context, color: Color, thickness: f32) {
+        let Self { start, end } = self;
+        ctx.draw_line_thick(start.x, start.y, end.x, end.y, thickness, color);
+    }
+}
+
+/// A point, the outline is a dot `thickness` pixels wide.
+impl Drawable for Vec2 {
+    fn fill_with_ctx(&self, ctx: &mut Context, paint: &Paint) {
+        ctx.set_pixel(self.x, self.y, paint.color_at(self.x, self.y));
+    }
+
+    fn outline_with_ctx(&self, ctx: &mut Context, color: Color, thickness: f32) {
+        let t = ctx.transform().unwrap_or_default();
+        let (point, thickness) = (t.apply(*self), thickness * t.scale);
+        ctx.untransformed(|ctx| ctx.stroke_polyline(&[point], false, thickness, color));
+    }
+}
+
+impl Drawable for Path {
+    fn fill_with_ctx(&self, ctx: &mut Context, paint: &Paint) {
+        ctx.fill_path(self, paint.clone());
+    }
+
+    fn outline_with_ctx(&self, ctx: &mut Context, color: Color, thickness: f32) {
+        ctx.stroke_path(self, thickness, color);
+    }
+}
@@ -1,87 +1,133 @@
-use crate::{Color, Context, Pixel};
-use crossterm::{cursor, queue, style};
-use std::io::Write;
+use crate::{Color, Context, Paint, Pixel, Transform, Vec2, Vec2d, BLANK};
+use std::ops::Range;
+
+mod curved;
+mod lines;
+mod outlines;
+mod polygon;
+pub use polygon::*;
 
 impl Context {
     pub fn commit_drawing_buffer_to_display(&mut self) {
-        let mut stdout = std::io::stdout();
-        // queue!(stdout, terminal::BeginSynchronizedUpdate).unwrap();
-        for y in 0..self.height() as usize {
-            for x in 0..self.width() as usize {
-                let fg = self.drawing_buffer.get(x, y * 2);
-                let bg = self.drawing_buffer.get(x, y * 2 + 1);
-                if fg == self.display_buffer.get(x, y * 2)
-                    && bg == self.display_buffer.get(x, y * 2 + 1)
-                {
-                    continue;
+        let frame = self.scale_to_display(self.composite_layers());
+        self.backend.present(&self.display_buffer, &frame).unwrap();
+        self.display_buffer = frame;
+    }
+
+    pub fn set_pixel(&mut self, x: f32, y: f32, color: Color) {
+        if let Some(t) = self.transform() {
+            return self.untransformed(|ctx| {
+                if t.scale > 1. {
+                    let corners = rect_points(x.floor(), y.floor(), 1., 1.).map(|p| t.apply(p));
+                    ctx.fill_polygon(&corners, color);
+                } else {
+                    let p = t.apply(Vec2::new(x, y));
+                    ctx.set_pixel(p.x, p.y, color);
                 }
-                queue!(
-                    stdout,
-                    cursor::MoveTo(x as u16, y as u16),
-                    style::SetForegroundColor(fg.into()),
-                    style::SetBackgroundColor(bg.into()),
-                    style::Print('▀'),
-                )
-                .unwrap();
-            }
+            });
         }
-        // queue!(stdout, terminal::EndSynchronizedUpdate).unwrap();
-        self.display_buffer = self.drawing_buffer.clone();
-        stdout.flush().unwrap();
+        let (x_start, y_start, x_end, y_end) = self.clip_bounds();
+        let (x, y) = (x.floor(), y.floor());
+        if x < x_start as f32 || y < y_start as f32 || x >= x_end as f32 || y >= y_end as f32 {
+            return;
+        }
+        let mode = self.blend_mode;
+        self.canvas_mut().blend(x as usize, y as usize, color, mode);
     }
 
-    pub fn set_pixel(&mut self, x: f32, y: f32, color: Color) {
-        // TODO: alpha
-        self.drawing_buffer.set(
-            x as usize,
-            y as usize,
-            Pixel {
-                r: color.r,
-                g: color.g,
-                b: color.b,
-            },
-        );
+    /// [`BLANK`] outside of the screen, sprite or window being drawn to.
+    pub fn get_pixel(&self, x: f32, y: f32) -> Color {
+        let (x, y) = ((x + self.origin.x).floor(), (y + self.origin.y).floor());
+        let (x_start, y_start, x_end, y_end) = self.clip_bounds();
+        if !(x >= x_start as f32 && x < x_end as f32 && y >= y_start as f32 && y < y_end as f32) {
+            return BLANK;
+        }
+        self.canvas().get(x as usize, y as usize)
     }
 
-    pub fn fill_rect(&mut self, x: f32, y: f32, width: f32, height: f32, color: Color) {
-        let (x, y, width, height) = (x as usize, y as usize, width as usize, height as usize);
-        let x_end = x + width;
-        let y_end = y + height;
-        for x in x..x_end {
-            if x >= self.drawing_buffer.width() {
-                break;
-            }
-            for y in y..y_end {
-                if y >= self.drawing_buffer.height() {
-                    break;
+    pub fn drawing_buffer(&self) -> &Vec2d<Pixel> {
+        &self.drawing_buffer
+    }
+
+    pub fn display_buffer(&self) -> &Vec2d<Pixel> {
+        &self.display_buffer
+    }
+
+    pub fn fill_rect<P>(&mut self, x: f32, y: f32, width: f32, height: f32, paint: P)
+    where
+        P: Into<Paint>,
+    {
+        let paint = paint.into();
+        if let Some(t) = self.transform() {
+            let paint = paint.transformed(&t);
+            return self.untransformed(|ctx| {
+                if t.has_rotation() {
+                    let corners = rect_points(x, y, width, height).map(|p| t.apply(p));
+                    ctx.fill_polygon(&corners, paint);
+                } else {
+                    let p = t.apply(Vec2::new(x, y));
+                    ctx.fill_rect(p.x, p.y, width * t.scale, height * t.scale, paint);
                 }
-                self.set_pixel(x as f32, y as f32, color);
-            }
+            });
+        }
+        let (x, y) = (x.floor(), y.floor());
+        let (width, height) = (width.max(0.).trunc(), height.max(0.).trunc());
+        // clipped in floats first, so huge rectangles don't overflow or loop forever
+        let (clip_x_start, _, clip_x_end, _) = self.clip_bounds();
+        let x_start = x.max(clip_x_start as f32) as i32;
+        let x_end = (x + width).min(clip_x_end as f32) as i32;
+        for my in self.visible_rows(y, y + height - 1.) {
+            self.fill_span(my, x_start, x_end, &paint);
         }
     }
 
-    pub fn clear_background(&mut self, color: Color) {
-        self.drawing_buffer.fill(Pixel {
-            r: color.r,
-            g: color.g,
-            b: color.b,
-        });
+    /// Fills the whole screen or sprite being drawn to (or the clipping rectangle),
+    /// ignoring the camera and blend mode.
+    pub fn clear_background<P>(&mut self, paint: P)
+    where
+        P: Into<Paint>,
+    {
+        let paint = paint
+            .into()
+            .transformed(&Transform::translation(self.origin));
+        if let (Paint::Solid(color), None) = (&paint, self.clip_stack.last()) {
+            self.canvas_mut().fill(*color);
+            return;
+        }
+        let (x_start, y_start, x_end, y_end) = self.clip_bounds();
+        for y in y_start as usize..y_end as usize {
+            for x in x_start as usize..x_end as usize {
+                let color = paint.color_at(x as f32, y as f32);
+                self.canvas_mut().replace(x, y, color);
+            }
+        }
     }
 
-    pub fn fill_circle(&mut self, x: f32, y: f32, radius: f32, color: Color) {
-        for mx in 0..self.drawing_buffer.width() {
-            for my in 0..self.drawing_buffer.height() {
-                let ox = mx as f32 - x;
-                let oy = my as f32 - y;
-                let d = (ox * ox + oy * oy).sqrt();
-                if d < radius {
-                    self.set_pixel(mx as f32, my as f32, color);
-                }
+    pub fn fill_circle<P>(&mut self, x: f32, y: f32, radius: f32, paint: P)
+    where
+        P: Into<Paint>,
+    {
+        let paint = paint.into();
+        if let Some(t) = self.transform() {
+            let c = t.apply(Vec2::new(x, y));
+            let paint = paint.transformed(&t);
+            return self.untransformed(|ctx| ctx.fill_circle(c.x, c.y, radius * t.scale, paint));
+        }
+        for my in self.visible_rows((y - radius).floor(), (y + radius).ceil()) {
+            let dy = my as f32 - y;
+            let half_sq = radius * radius - dy * dy;
+            if half_sq <= 0. {
+                continue;
             }
+            let half = half_sq.sqrt();
+            let x_start = (x - half).floor() as i32 + 1;
+            let x_end = (x + half).ceil() as i32;
+            self.fill_span(my, x_start, x_end, &paint);
         }
     }
 
-    pub fn fill_triangle(
+    #[allow(clippy::too_many_arguments)]
+    pub fn fill_triangle<P>(
         &mut self,
         x1: f32,
         y1: f32,
@@ -89,23 +135,100 @@ impl Context {
         y2: f32,
         x3: f32,
         y3: f32,
-        color: Color,
-    ) {
-        let area = |x1: f32, y1: f32, x2: f32, y2: f32, x3: f32, y3: f32| {
-            ((x1 * (y2 - y3) + x2 * (y3 - y1) + x3 * (y1 - y2)) / 2.0).abs()
-        };
-        for mx in 0..self.drawing_buffer.width() {
-            for my in 0..self.drawing_buffer.height() {
-                let x = mx as f32;
-                let y = my as f32;
-                let a = area(x1, y1, x2, y2, x3, y3);
-                let b = area(x, y, x2, y2, x3, y3);
-                let c = area(x1, y1, x, y, x3, y3);
-                let d = area(x1, y1, x2, y2, x, y);
-                if a == b + c + d {
-                    self.set_pixel(mx as f32, my as f32, color);
+        paint: P,
+    ) where
+        P: Into<Paint>,
+    {
+        let paint = paint.into();
+        let (a, mut b, mut c) = (Vec2::new(x1, y1), Vec2::new(x2, y2), Vec2::new(x3, y3));
+        if let Some(t) = self.transform() {
+            let (a, b, c) = (t.apply(a), t.apply(b), t.apply(c));
+            let paint = paint.transformed(&t);
+            return self
+                .untransformed(|ctx| ctx.fill_triangle(a.x, a.y, b.x, b.y, c.x, c.y, paint));
+        }
+        let area = cross(b - a, c - a);
+        if area == 0. || !area.is_finite() {
+            return;
+        }
+        if area < 0. {
+            std::mem::swap(&mut b, &mut c);
+        }
+        let edges = [(a, b), (b, c), (c, a)];
+
+        let y_start = a.y.min(b.y).min(c.y).ceil();
+        let y_end = a.y.max(b.y).max(c.y).floor();
+        for my in self.visible_rows(y_start, y_end) {
+            let y = my as f32;
+            let (mut lo, mut hi) = (f32::NEG_INFINITY, f32::INFINITY);
+            for (p, q) in edges {
+                // cross(q - p, (x, y) - p) >= 0, solved for x
+                let dx = q.x - p.x;
+                let dy = q.y - p.y;
+                let c = dx * (y - p.y) + dy * p.x;
+                if dy > 0. {
+                    hi = hi.min(c / dy);
+                } else if dy < 0. {
+                    lo = lo.max(c / dy);
+                } else if c < 0. {
+                    hi = f32::NEG_INFINITY;
                 }
             }
+            if lo <= hi {
+                self.fill_span(my, lo.ceil() as i32, hi.floor() as i32 + 1, &paint);
+            }
+        }
+    }
+
+    /// Rows `y_start..=y_end` that aren't clipped, so huge shapes only visit the rows
+    /// that can show.
+    pub(crate) fn visible_rows(&self, y_start: f32, y_end: f32) -> Range<i32> {
+        let (_, clip_y_start, _, clip_y_end) = self.clip_bounds();
+        let start = y_start.max(clip_y_start as f32) as i32;
+        let end = (y_end + 1.).min(clip_y_end as f32) as i32;
+        start..end.max(start)
+    }
+
+    /// Draws pixels `x_start..x_end` of row `y`, skipping whatever is clipped.
+    pub(crate) fn fill_span(&mut self, y: i32, x_start: i32, x_end: i32, paint: &Paint) {
+        let (clip_x_start, clip_y_start, clip_x_end, clip_y_end) = self.clip_bounds();
+        if y < clip_y_start || y >= clip_y_end {
+            return;
+        }
+        let x_start = x_start.max(clip_x_start) as usize;
+        let x_end = x_end.min(clip_x_end).max(0) as usize;
+        let mode = self.blend_mode;
+        let canvas = self.canvas_mut();
+        for x in x_start..x_end {
+            let color = match paint {
+                Paint::Solid(color) => *color,
+                paint => paint.color_at(x as f32, y as f32),
+            };
+            canvas.blend(x, y as usize, color, mode);
         }
     }
 }
+
+fn cross(a: Vec2, b: Vec2) -> f32 {
+    a.x * b.y - a.y * b.x
+}
+
+pub(crate) fn rect_points(x: f32, y: f32, width: f32, height: f32) -> [Vec2; 4] {
+    [
+        Vec2::new(x, y),
+        Vec2::new(x + width, y),
+        Vec2::new(x + width, y + height),
+        Vec2::new(x, y + height),
+    ]
+}
+
+/// Enough points around the ellipse for it to look smooth at `scale`.
+pub(crate) fn ellipse_points(center: Vec2, radius_x: f32, radius_y: f32, scale: f32) -> Vec<Vec2> {
+    let segments = ((radius_x + radius_y) * scale * 2.).clamp(12., 360.) as usize;
+    (0..segments)
+        .map(|i| {
+            let angle = i as f32 / segments as f32 * std::f32::consts::TAU;
+            center + Vec2::new(angle.cos() * radius_x, angle.sin() * radius_y)
+        })
+        .collect()
+}
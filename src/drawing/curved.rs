@@ -0,0 +1,276 @@
+use super::ellipse_points;
+use crate::{Color, Context, FillRule, Paint, Vec2};
+use std::f32::consts::{FRAC_PI_2, PI, TAU};
+
+impl Context {
+    pub fn fill_ellipse<P>(&mut self, x: f32, y: f32, radius_x: f32, radius_y: f32, paint: P)
+    where
+        P: Into<Paint>,
+    {
+        let paint = paint.into();
+        if radius_x <= 0. || radius_y <= 0. {
+            return;
+        }
+        if let Some(t) = self.transform() {
+            let paint = paint.transformed(&t);
+            let center = Vec2::new(x, y);
+            if !t.has_rotation() || radius_x == radius_y {
+                let c = t.apply(center);
+                let (rx, ry) = (radius_x * t.scale, radius_y * t.scale);
+                return self.untransformed(|ctx| ctx.fill_ellipse(c.x, c.y, rx, ry, paint));
+            }
+            let points = ellipse_points(center, radius_x, radius_y, t.scale)
+                .into_iter()
+                .map(|p| t.apply(p))
+                .collect::<Vec<_>>();
+            return self.untransformed(|ctx| ctx.fill_polygon(&points, paint));
+        }
+        for my in self.visible_rows((y - radius_y).floor(), (y + radius_y).ceil()) {
+            let dy = (my as f32 - y) / radius_y;
+            let half_sq = 1. - dy * dy;
+            if half_sq <= 0. {
+                continue;
+            }
+            let half = radius_x * half_sq.sqrt();
+            let x_start = (x - half).floor() as i32 + 1;
+            let x_end = (x + half).ceil() as i32;
+            self.fill_span(my, x_start, x_end, &paint);
+        }
+    }
+
+    /// Part of a circle outline going clockwise from `start_angle` to `end_angle` (in radians).
+    /// The outline is drawn inside the circle.
+    #[allow(clippy::too_many_arguments)]
+    pub fn draw_arc(
+        &mut self,
+        x: f32,
+        y: f32,
+        radius: f32,
+        start_angle: f32,
+        end_angle: f32,
+        thickness: f32,
+        color: Color,
+    ) {
+        if let Some(t) = self.transform() {
+            let c = t.apply(Vec2::new(x, y));
+            let (start, end) = (start_angle + t.rotation, end_angle + t.rotation);
+            let (radius, thickness) = (radius * t.scale, thickness * t.scale);
+            return self
+                .untransformed(|ctx| ctx.draw_arc(c.x, c.y, radius, start, end, thickness, color));
+        }
+        let inner = radius - thickness;
+        let paint = Paint::Solid(color);
+        self.fill_sector_where(x, y, radius, start_angle, end_angle, &paint, |d| d >= inner);
+    }
+
+    /// A "pizza slice" going clockwise from `start_angle` to `end_angle` (in radians).
+    pub fn fill_pie<P>(
+        &mut self,
+        x: f32,
+        y: f32,
+        radius: f32,
+        start_angle: f32,
+        end_angle: f32,
+        paint: P,
+    ) where
+        P: Into<Paint>,
+    {
+        let paint = paint.into();
+        if let Some(t) = self.transform() {
+            let c = t.apply(Vec2::new(x, y));
+            let (start, end) = (start_angle + t.rotation, end_angle + t.rotation);
+            let paint = paint.transformed(&t);
+            return self
+                .untransformed(|ctx| ctx.fill_pie(c.x, c.y, radius * t.scale, start, end, paint));
+        }
+        self.fill_sector_where(x, y, radius, start_angle, end_angle, &paint, |_| true);
+    }
+
+    pub fn fill_rounded_rect<P>(
+        &mut self,
+        x: f32,
+        y: f32,
+        width: f32,
+        height: f32,
+        radius: f32,
+        paint: P,
+    ) where
+        P: Into<Paint>,
+    {
+        let paint = paint.into();
+        if let Some(t) = self.transform() {
+            let paint = paint.transformed(&t);
+            if !t.has_rotation() {
+                let p = t.apply(Vec2::new(x, y));
+                let (w, h, r) = (width * t.scale, height * t.scale, radius * t.scale);
+                return self.untransformed(|ctx| ctx.fill_rounded_rect(p.x, p.y, w, h, r, paint));
+            }
+            let points = rounded_rect_points(x, y, width, height, radius, t.scale)
+                .into_iter()
+                .map(|p| t.apply(p))
+                .collect::<Vec<_>>();
+            return self.untransformed(|ctx| ctx.fill_polygon(&points, paint));
+        }
+        for my in self.visible_rows(y.ceil(), (y + height - 1.).floor()) {
+            if let Some((x_start, x_end)) = rounded_rect_span(x, y, width, height, radius, my) {
+                self.fill_span(my, x_start, x_end, &paint);
+            }
+        }
+    }
+
+    /// The outline is drawn inside the rectangle.
+    #[allow(clippy::too_many_arguments)]
+    pub fn draw_rounded_rect(
+        &mut self,
+        x: f32,
+        y: f32,
+        width: f32,
+        height: f32,
+        radius: f32,
+        thickness: f32,
+        color: Color,
+    ) {
+        let paint = Paint::Solid(color);
+        if let Some(t) = self.transform() {
+            if !t.has_rotation() {
+                let p = t.apply(Vec2::new(x, y));
+                let (w, h, r) = (width * t.scale, height * t.scale, radius * t.scale);
+                let thickness = thickness * t.scale;
+                return self.untransformed(|ctx| {
+                    ctx.draw_rounded_rect(p.x, p.y, w, h, r, thickness, color)
+                });
+            }
+            let th = thickness.min(width.min(height) / 2.);
+            let contours = [
+                rounded_rect_points(x, y, width, height, radius, t.scale),
+                rounded_rect_points(
+                    x + th,
+                    y + th,
+                    width - th * 2.,
+                    height - th * 2.,
+                    radius - th,
+                    t.scale,
+                ),
+            ]
+            .map(|points| points.into_iter().map(|p| t.apply(p)).collect::<Vec<_>>());
+            return self
+                .untransformed(|ctx| ctx.fill_contours(&contours, FillRule::EvenOdd, &paint));
+        }
+        let t = thickness;
+        for my in self.visible_rows(y.ceil(), (y + height - 1.).floor()) {
+            let Some((x_start, x_end)) = rounded_rect_span(x, y, width, height, radius, my) else {
+                continue;
+            };
+            let inner = rounded_rect_span(
+                x + t,
+                y + t,
+                width - t * 2.,
+                height - t * 2.,
+                radius - t,
+                my,
+            );
+            match inner {
+                Some((inner_start, inner_end)) if inner_start < inner_end => {
+                    self.fill_span(my, x_start, inner_start, &paint);
+                    self.fill_span(my, inner_end, x_end, &paint);
+                }
+                _ => self.fill_span(my, x_start, x_end, &paint),
+            }
+        }
+    }
+
+    #[allow(clippy::too_many_arguments)]
+    fn fill_sector_where<F>(
+        &mut self,
+        x: f32,
+        y: f32,
+        radius: f32,
+        start_angle: f32,
+        end_angle: f32,
+        paint: &Paint,
+        keep: F,
+    ) where
+        F: Fn(f32) -> bool,
+    {
+        // angles wrap around, so `end_angle < start_angle` still goes clockwise to it
+        let span = end_angle - start_angle;
+        let span = if span.abs() >= TAU {
+            TAU
+        } else {
+            span.rem_euclid(TAU)
+        };
+        let (clip_x_start, _, clip_x_end, _) = self.clip_bounds();
+        let x_start = (x - radius).floor().max(clip_x_start as f32) as i32;
+        let x_end = ((x + radius).ceil() + 1.).min(clip_x_end as f32) as i32;
+        for my in self.visible_rows((y - radius).floor(), (y + radius).ceil()) {
+            for mx in x_start..x_end {
+                let dx = mx as f32 - x;
+                let dy = my as f32 - y;
+                let d = (dx * dx + dy * dy).sqrt();
+                if d >= radius || !keep(d) {
+                    continue;
+                }
+                let angle = (dy.atan2(dx) - start_angle).rem_euclid(TAU);
+                if span >= TAU || (d == 0. && span > 0.) || angle <= span {
+                    let color = paint.color_at(mx as f32, my as f32);
+                    self.set_pixel(mx as f32, my as f32, color);
+                }
+            }
+        }
+    }
+}
+
+/// Outline of a rounded rectangle, with enough points to look smooth at `scale`.
+fn rounded_rect_points(
+    x: f32,
+    y: f32,
+    width: f32,
+    height: f32,
+    radius: f32,
+    scale: f32,
+) -> Vec<Vec2> {
+    let radius = radius.clamp(0., width.min(height).max(0.) / 2.);
+    let segments = (radius * scale).clamp(1., 90.) as usize;
+    let corners = [
+        (Vec2::new(x + width - radius, y + radius), -FRAC_PI_2),
+        (Vec2::new(x + width - radius, y + height - radius), 0.),
+        (Vec2::new(x + radius, y + height - radius), FRAC_PI_2),
+        (Vec2::new(x + radius, y + radius), PI),
+    ];
+    corners
+        .into_iter()
+        .flat_map(|(center, start)| {
+            (0..=segments).map(move |i| {
+                let angle = start + i as f32 / segments as f32 * FRAC_PI_2;
+                center + Vec2::angled(angle) * radius
+            })
+        })
+        .collect()
+}
+
+/// Pixels `start..end` of row `my` inside the rounded rectangle. Like the other fills
+/// it's sampled at integer coordinates, going from `x` to `x + width - 1` so that with
+/// no radius it covers the same pixels as [`Context::fill_rect`].
+fn rounded_rect_span(
+    x: f32,
+    y: f32,
+    width: f32,
+    height: f32,
+    radius: f32,
+    my: i32,
+) -> Option<(i32, i32)> {
+    if width <= 0. || height <= 0. {
+        return None;
+    }
+    let (width, height) = (width - 1., height - 1.);
+    let radius = radius.clamp(0., width.min(height).max(0.) / 2.);
+    let cy = my as f32;
+    if cy < y || cy > y + height {
+        return None;
+    }
+    let dy = (y + radius - cy).max(cy - (y + height - radius)).max(0.);
+    let inset = radius - (radius * radius - dy * dy).max(0.).sqrt();
+    let x_start = (x + inset).ceil() as i32;
+    let x_end = (x + width - inset).floor() as i32 + 1;
+    Some((x_start, x_end))
+}
@@ -0,0 +1,232 @@
+use crate::{Color, Context, Paint, Vec2};
+
+impl Context {
+    pub fn draw_line(&mut self, x1: f32, y1: f32, x2: f32, y2: f32, color: Color) {
+        self.paint_line(Vec2::new(x1, y1), Vec2::new(x2, y2), &Paint::Solid(color));
+    }
+
+    /// A 1 pixel wide line, each pixel colored by `paint`.
+    pub(crate) fn paint_line(&mut self, a: Vec2, b: Vec2, paint: &Paint) {
+        if let Some(t) = self.transform() {
+            let (a, b, paint) = (t.apply(a), t.apply(b), paint.transformed(&t));
+            return self.untransformed(|ctx| ctx.paint_line(a, b, &paint));
+        }
+        let Some((a, b)) = self.clip_line(a, b) else {
+            return;
+        };
+        let (mut x, mut y) = (a.x.floor() as i32, a.y.floor() as i32);
+        let (x_end, y_end) = (b.x.floor() as i32, b.y.floor() as i32);
+        let dx = (x_end - x).abs();
+        let dy = -(y_end - y).abs();
+        let step_x = if x < x_end { 1 } else { -1 };
+        let step_y = if y < y_end { 1 } else { -1 };
+        let mut err = dx + dy;
+        loop {
+            let (px, py) = (x as f32, y as f32);
+            self.set_pixel(px, py, paint.color_at(px, py));
+            if x == x_end && y == y_end {
+                break;
+            }
+            let e2 = err * 2;
+            if e2 >= dy {
+                err += dy;
+                x += step_x;
+            }
+            if e2 <= dx {
+                err += dx;
+                y += step_y;
+            }
+        }
+    }
+
+    pub fn draw_line_thick(
+        &mut self,
+        x1: f32,
+        y1: f32,
+        x2: f32,
+        y2: f32,
+        thickness: f32,
+        color: Color,
+    ) {
+        if let Some(t) = self.transform() {
+            let (a, b) = (t.apply(Vec2::new(x1, y1)), t.apply(Vec2::new(x2, y2)));
+            let thickness = thickness * t.scale;
+            return self
+                .untransformed(|ctx| ctx.draw_line_thick(a.x, a.y, b.x, b.y, thickness, color));
+        }
+        if thickness <= 1. {
+            self.draw_line(x1, y1, x2, y2, color);
+        } else {
+            self.stroke_polyline(
+                &[Vec2::new(x1, y1), Vec2::new(x2, y2)],
+                false,
+                thickness,
+                color,
+            );
+        }
+    }
+
+    /// Anti-aliased line using Xiaolin Wu's algorithm.
+    pub fn draw_line_aa(&mut self, x1: f32, y1: f32, x2: f32, y2: f32, color: Color) {
+        if let Some(t) = self.transform() {
+            let (a, b) = (t.apply(Vec2::new(x1, y1)), t.apply(Vec2::new(x2, y2)));
+            return self.untransformed(|ctx| ctx.draw_line_aa(a.x, a.y, b.x, b.y, color));
+        }
+        let Some((a, b)) = self.clip_line(Vec2::new(x1, y1), Vec2::new(x2, y2)) else {
+            return;
+        };
+        let (x1, y1, x2, y2) = (a.x, a.y, b.x, b.y);
+        let steep = (y2 - y1).abs() > (x2 - x1).abs();
+        let (mut x1, mut y1, mut x2, mut y2) = if steep {
+            (y1, x1, y2, x2)
+        } else {
+            (x1, y1, x2, y2)
+        };
+        if x1 > x2 {
+            std::mem::swap(&mut x1, &mut x2);
+            std::mem::swap(&mut y1, &mut y2);
+        }
+        let gradient = if x2 == x1 { 1. } else { (y2 - y1) / (x2 - x1) };
+
+        let plot = |ctx: &mut Self, x: f32, y: f32, coverage: f32| {
+            let a = (color.a as f32 * coverage.clamp(0., 1.)).round() as u8;
+            let color = Color::rgba(color.r, color.g, color.b, a);
+            if steep {
+                ctx.set_pixel(y, x, color);
+            } else {
+                ctx.set_pixel(x, y, color);
+            }
+        };
+
+        // `f32::fract` is negative for negative numbers
+        let fract = |v: f32| v - v.floor();
+
+        let x_start = x1.round();
+        let x_gap = 1. - fract(x1 + 0.5);
+        let y_start = y1 + gradient * (x_start - x1);
+        plot(
+            self,
+            x_start,
+            y_start.floor(),
+            (1. - fract(y_start)) * x_gap,
+        );
+        plot(self, x_start, y_start.floor() + 1., fract(y_start) * x_gap);
+
+        let x_end = x2.round();
+        let x_gap = fract(x2 + 0.5);
+        let y_end = y2 + gradient * (x_end - x2);
+        plot(self, x_end, y_end.floor(), (1. - fract(y_end)) * x_gap);
+        plot(self, x_end, y_end.floor() + 1., fract(y_end) * x_gap);
+
+        for i in 1..(x_end - x_start) as i32 {
+            let x = x_start + i as f32;
+            let y = y_start + gradient * i as f32;
+            plot(self, x, y.floor(), 1. - fract(y));
+            plot(self, x, y.floor() + 1., fract(y));
+        }
+    }
+
+    /// Cuts the segment to the clip rectangle with a small margin, so lines far
+    /// off screen cost nothing and their coordinates stay small.
+    fn clip_line(&self, a: Vec2, b: Vec2) -> Option<(Vec2, Vec2)> {
+        let (x0, y0, x1, y1) = self.clip_bounds();
+        if x0 >= x1 || y0 >= y1 {
+            return None;
+        }
+        let min = Vec2::new(x0 as f32 - 2., y0 as f32 - 2.);
+        let max = Vec2::new(x1 as f32 + 2., y1 as f32 + 2.);
+        if ![a.x, a.y, b.x, b.y].iter().all(|v| v.is_finite()) {
+            return None;
+        }
+        let inside = |p: Vec2| p.x >= min.x && p.x <= max.x && p.y >= min.y && p.y <= max.y;
+        if inside(a) && inside(b) {
+            return Some((a, b));
+        }
+        // Liang-Barsky, in f64 so huge coordinates keep their precision
+        let (ax, ay) = (a.x as f64, a.y as f64);
+        let (dx, dy) = (b.x as f64 - ax, b.y as f64 - ay);
+        let (mut t0, mut t1) = (0f64, 1f64);
+        for (p, q) in [
+            (-dx, ax - min.x as f64),
+            (dx, max.x as f64 - ax),
+            (-dy, ay - min.y as f64),
+            (dy, max.y as f64 - ay),
+        ] {
+            if p == 0. {
+                if q < 0. {
+                    return None;
+                }
+            } else if p < 0. {
+                t0 = t0.max(q / p);
+            } else {
+                t1 = t1.min(q / p);
+            }
+        }
+        if t0 > t1 {
+            return None;
+        }
+        let at = |t: f64| Vec2::new((ax + dx * t) as f32, (ay + dy * t) as f32);
+        Some((at(t0), at(t1)))
+    }
+
+    /// Draws every pixel within `thickness / 2` of the polyline, each pixel at most once.
+    pub(crate) fn stroke_polyline(
+        &mut self,
+        points: &[Vec2],
+        closed: bool,
+        thickness: f32,
+        color: Color,
+    ) {
+        if points.is_empty() {
+            return;
+        }
+        let radius = thickness / 2.;
+        let (min, max) = points.iter().fold((Vec2::MAX, Vec2::MIN), |(min, max), p| {
+            (min.min(p), max.max(p))
+        });
+        let x_start = (min.x - radius).floor().max(0.) as usize;
+        let y_start = (min.y - radius).floor().max(0.) as usize;
+        let x_end = ((max.x + radius).ceil() + 1.).clamp(0., self.screen_width()) as usize;
+        let y_end = ((max.y + radius).ceil() + 1.).clamp(0., self.screen_height()) as usize;
+        if x_start >= x_end || y_start >= y_end {
+            return;
+        }
+
+        let width = x_end - x_start;
+        let mut mask = vec![false; width * (y_end - y_start)];
+        let segments = points.windows(2).map(|w| (w[0], w[1]));
+        let closing = (closed && points.len() > 2).then(|| (points[points.len() - 1], points[0]));
+        let single = (points.len() == 1).then(|| (points[0], points[0]));
+        for (a, b) in segments.chain(closing).chain(single) {
+            let sx = ((a.x.min(b.x) - radius).floor().max(0.) as usize).max(x_start);
+            let sy = ((a.y.min(b.y) - radius).floor().max(0.) as usize).max(y_start);
+            let ex = (((a.x.max(b.x) + radius).ceil() + 1.).max(0.) as usize).min(x_end);
+            let ey = (((a.y.max(b.y) + radius).ceil() + 1.).max(0.) as usize).min(y_end);
+            for y in sy..ey {
+                for x in sx..ex {
+                    let p = Vec2::new(x as f32, y as f32);
+                    if distance_to_segment(p, a, b) <= radius {
+                        mask[(y - y_start) * width + x - x_start] = true;
+                    }
+                }
+            }
+        }
+
+        for (i, _) in mask.iter().enumerate().filter(|(_, m)| **m) {
+            let x = x_start + i % width;
+            let y = y_start + i / width;
+            self.set_pixel(x as f32, y as f32, color);
+        }
+    }
+}
+
+pub(crate) fn distance_to_segment(p: Vec2, a: Vec2, b: Vec2) -> f32 {
+    let ab = b - a;
+    let len_sq = ab.dot(&ab);
+    let t = if len_sq == 0. {
+        0.
+    } else {
+        ((p - a).dot(&ab) / len_sq).clamp(0., 1.)
+    };
+    p.distance(&(a + ab * t))
+}
@@ -0,0 +1,126 @@
+use super::{ellipse_points, rect_points};
+use crate::{Color, Context, FillRule, Paint, Vec2};
+
+impl Context {
+    /// The outline is drawn inside the rectangle.
+    pub fn draw_rect(
+        &mut self,
+        x: f32,
+        y: f32,
+        width: f32,
+        height: f32,
+        thickness: f32,
+        color: Color,
+    ) {
+        if let Some(t) = self.transform() {
+            let transform = |points: [Vec2; 4]| points.map(|p| t.apply(p));
+            let outer = transform(rect_points(x, y, width, height));
+            let th = thickness.min(width.min(height) / 2.);
+            let inner = transform(rect_points(
+                x + th,
+                y + th,
+                width - th * 2.,
+                height - th * 2.,
+            ));
+            let paint = Paint::Solid(color);
+            return self.untransformed(|ctx| {
+                ctx.fill_contours(&[outer, inner], FillRule::EvenOdd, &paint)
+            });
+        }
+        if thickness * 2. >= width.min(height) {
+            self.fill_rect(x, y, width, height, color);
+            return;
+        }
+        let t = thickness;
+        self.fill_rect(x, y, width, t, color);
+        self.fill_rect(x, y + height - t, width, t, color);
+        self.fill_rect(x, y + t, t, height - t * 2., color);
+        self.fill_rect(x + width - t, y + t, t, height - t * 2., color);
+    }
+
+    /// The outline is drawn inside the circle.
+    pub fn draw_circle(&mut self, x: f32, y: f32, radius: f32, thickness: f32, color: Color) {
+        self.draw_ellipse(x, y, radius, radius, thickness, color);
+    }
+
+    /// The outline is drawn inside the ellipse.
+    pub fn draw_ellipse(
+        &mut self,
+        x: f32,
+        y: f32,
+        radius_x: f32,
+        radius_y: f32,
+        thickness: f32,
+        color: Color,
+    ) {
+        if let Some(t) = self.transform() {
+            let center = t.apply(Vec2::new(x, y));
+            let (rx, ry, th) = (radius_x * t.scale, radius_y * t.scale, thickness * t.scale);
+            if !t.has_rotation() || radius_x == radius_y {
+                return self
+                    .untransformed(|ctx| ctx.draw_ellipse(center.x, center.y, rx, ry, th, color));
+            }
+            let rotate = |points: Vec<Vec2>| {
+                points
+                    .into_iter()
+                    .map(|p| p.rotate_around(&center, t.rotation))
+                    .collect::<Vec<_>>()
+            };
+            let outer = rotate(ellipse_points(center, rx, ry, 1.));
+            let inner = rotate(ellipse_points(
+                center,
+                (rx - th).max(0.),
+                (ry - th).max(0.),
+                1.,
+            ));
+            let paint = Paint::Solid(color);
+            return self.untransformed(|ctx| {
+                ctx.fill_contours(&[outer, inner], FillRule::EvenOdd, &paint)
+            });
+        }
+        let inside = |dx: f32, dy: f32, rx: f32, ry: f32| {
+            rx > 0. && ry > 0. && (dx / rx).powi(2) + (dy / ry).powi(2) < 1.
+        };
+        let (inner_x, inner_y) = (radius_x - thickness, radius_y - thickness);
+        let x_start = (x - radius_x).floor().max(0.) as usize;
+        let y_start = (y - radius_y).floor().max(0.) as usize;
+        let x_end = ((x + radius_x).ceil() + 1.).clamp(0., self.screen_width()) as usize;
+        let y_end = ((y + radius_y).ceil() + 1.).clamp(0., self.screen_height()) as usize;
+        for my in y_start..y_end {
+            for mx in x_start..x_end {
+                let dx = mx as f32 - x;
+                let dy = my as f32 - y;
+                if inside(dx, dy, radius_x, radius_y) && !inside(dx, dy, inner_x, inner_y) {
+                    self.set_pixel(mx as f32, my as f32, color);
+                }
+            }
+        }
+    }
+
+    /// The outline is centered on the edges.
+    #[allow(clippy::too_many_arguments)]
+    pub fn draw_triangle(
+        &mut self,
+        x1: f32,
+        y1: f32,
+        x2: f32,
+        y2: f32,
+        x3: f32,
+        y3: f32,
+        thickness: f32,
+        color: Color,
+    ) {
+        let points = [Vec2::new(x1, y1), Vec2::new(x2, y2), Vec2::new(x3, y3)];
+        self.draw_polygon(&points, thickness, color);
+    }
+
+    /// The outline is centered on the edges, the last point connects back to the first one.
+    pub fn draw_polygon(&mut self, points: &[Vec2], thickness: f32, color: Color) {
+        if let Some(t) = self.transform() {
+            let points = points.iter().map(|p| t.apply(*p)).collect::<Vec<_>>();
+            let thickness = thickness * t.scale;
+            return self.untransformed(|ctx| ctx.draw_polygon(&points, thickness, color));
+        }
+        self.stroke_polyline(points, true, thickness.max(1.), color);
+    }
+}
@@ -0,0 +1,90 @@
+use crate::{Context, Paint, Vec2};
+
+/// Decides which parts of a self-intersecting polygon are inside.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub enum FillRule {
+    /// Inside wherever the edges wind around a point at least once.
+    #[default]
+    NonZero,
+    /// Inside wherever a ray from a point crosses an odd number of edges.
+    EvenOdd,
+}
+
+impl FillRule {
+    fn is_inside(&self, winding: i32) -> bool {
+        match self {
+            FillRule::NonZero => winding != 0,
+            FillRule::EvenOdd => winding % 2 != 0,
+        }
+    }
+}
+
+impl Context {
+    /// Fills any polygon, concave and self-intersecting ones included.
+    pub fn fill_polygon<P>(&mut self, points: &[Vec2], paint: P)
+    where
+        P: Into<Paint>,
+    {
+        self.fill_polygon_with_rule(points, FillRule::default(), paint);
+    }
+
+    pub fn fill_polygon_with_rule<P>(&mut self, points: &[Vec2], rule: FillRule, paint: P)
+    where
+        P: Into<Paint>,
+    {
+        let paint = paint.into();
+        if let Some(t) = self.transform() {
+            let points = points.iter().map(|p| t.apply(*p)).collect::<Vec<_>>();
+            let paint = paint.transformed(&t);
+            return self.untransformed(|ctx| ctx.fill_contours(&[points], rule, &paint));
+        }
+        self.fill_contours(&[points], rule, &paint);
+    }
+
+    /// Fills several closed contours as one shape, so holes can be cut with the fill rule.
+    pub(crate) fn fill_contours<P>(&mut self, contours: &[P], rule: FillRule, paint: &Paint)
+    where
+        P: AsRef<[Vec2]>,
+    {
+        let edges = contours
+            .iter()
+            .map(AsRef::as_ref)
+            .filter(|c| c.len() > 2)
+            .flat_map(|c| c.iter().zip(c.iter().cycle().skip(1)))
+            .map(|(a, b)| (*a, *b))
+            .filter(|(a, b)| a.y != b.y)
+            .collect::<Vec<_>>();
+        let Some((min_y, max_y)) = edges.iter().fold(None, |range, (a, b)| {
+            let (lo, hi) = range.unwrap_or((f32::MAX, f32::MIN));
+            Some((lo.min(a.y).min(b.y), hi.max(a.y).max(b.y)))
+        }) else {
+            return;
+        };
+
+        let y_start = (min_y.ceil() as i32).max(0);
+        let y_end = (max_y.ceil() as i32).min(self.screen_height() as i32);
+        let mut crossings = Vec::new();
+        for my in y_start..y_end {
+            let y = my as f32;
+            crossings.clear();
+            for (a, b) in &edges {
+                let (winding, top, bottom) = if a.y < b.y { (1, a, b) } else { (-1, b, a) };
+                if top.y <= y && y < bottom.y {
+                    let x = top.x + (y - top.y) * (bottom.x - top.x) / (bottom.y - top.y);
+                    crossings.push((x, winding));
+                }
+            }
+            crossings.sort_by(|a, b| a.0.total_cmp(&b.0));
+
+            let mut winding = 0;
+            for pair in crossings.windows(2) {
+                winding += pair[0].1;
+                if rule.is_inside(winding) {
+                    let x_start = pair[0].0.ceil() as i32;
+                    let x_end = pair[1].0.ceil() as i32;
+                    self.fill_span(my, x_start, x_end, paint);
+                }
+            }
+        }
+    }
+}
@@ -1,13 +1,15 @@
 use crate::{Context, Pixel};
-use crossterm::event::{self, Event};
-use std::time::Duration;
+use crossterm::event::Event;
 
 impl Context {
     pub fn handle_events(&mut self) {
         self.previous_state = self.current_state.clone();
         self.current_state.mouse_positions.clear();
-        while event::poll(Duration::from_millis(0)).unwrap() {
-            let ev = event::read().unwrap();
+        while let Some(ev) = self
+            .pending_events
+            .pop_front()
+            .or_else(|| self.backend.poll_event().unwrap())
+        {
             match ev {
                 Event::Key(key) => self.handle_keyboard_event(key),
                 Event::Mouse(mouse) => self.handle_mouse_event(mouse),
@@ -22,17 +24,25 @@ impl Context {
         }
     }
 
+    /// Queues an event to be handled on the next frame as if it came from the backend.
+    pub fn push_event(&mut self, event: Event) {
+        self.pending_events.push_back(event);
+    }
+
     pub fn handle_resize_event(&mut self, width: u16, height: u16) {
         self.display_buffer.resize_with(
             width as usize,
             height as usize * 2,
             Pixel { r: 1, g: 2, b: 3 },
         );
-        self.drawing_buffer.resize_with(
-            width as usize,
-            height as usize * 2,
-            Pixel { r: 0, g: 0, b: 0 },
-        );
+        if self.virtual_resolution.is_none() {
+            self.drawing_buffer.resize_with(
+                width as usize,
+                height as usize * 2,
+                Pixel { r: 0, g: 0, b: 0 },
+            );
+            self.resize_layers();
+        }
         self.commit_drawing_buffer_to_display();
     }
 }
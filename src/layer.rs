@@ -0,0 +1,146 @@
+use crate::{BlendMode, Color, Context, Pixel, Vec2d, BLANK};
+
+/// How a layer is put on top of the ones below it when the frame is shown.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct LayerStyle {
+    /// Multiplies the alpha of every pixel in the layer, from 0 to 1.
+    pub opacity: f32,
+    pub visible: bool,
+    pub blend_mode: BlendMode,
+}
+
+impl Default for LayerStyle {
+    fn default() -> Self {
+        Self {
+            opacity: 1.,
+            visible: true,
+            blend_mode: BlendMode::Alpha,
+        }
+    }
+}
+
+/// A transparent buffer drawn over the screen, see [`Context::set_layer`].
+#[derive(Debug, Clone)]
+pub(crate) struct Layer {
+    pub(crate) buffer: Vec2d<Color>,
+    style: LayerStyle,
+}
+
+impl Context {
+    /// Sends everything drawn from now on to layer `index`.
+    /// Layer 0 is the screen itself, higher layers are shown on top of it in order
+    /// and start out transparent. There are no layers below the screen, it's opaque
+    /// so they would never show. Like the screen, layers keep what's drawn on them
+    /// between frames, clear them with a transparent [`Context::clear_background`].
+    pub fn set_layer(&mut self, index: u32) {
+        self.current_layer = index;
+        self.layer_mut(index);
+    }
+
+    pub fn layer(&self) -> u32 {
+        self.current_layer
+    }
+
+    /// Draws everything in `f` on layer `index`, then goes back to the previous layer.
+    pub fn with_layer<F, R>(&mut self, index: u32, f: F) -> R
+    where
+        F: FnOnce(&mut Self) -> R,
+    {
+        let previous = self.current_layer;
+        self.set_layer(index);
+        let result = f(self);
+        self.current_layer = previous;
+        result
+    }
+
+    pub fn layer_style(&self, index: u32) -> LayerStyle {
+        self.layers
+            .get(&index)
+            .map(|layer| layer.style)
+            .unwrap_or_default()
+    }
+
+    pub fn set_layer_style(&mut self, index: u32, style: LayerStyle) {
+        self.layer_mut(index).style = style;
+    }
+
+    pub fn set_layer_opacity(&mut self, index: u32, opacity: f32) {
+        self.layer_mut(index).style.opacity = opacity.clamp(0., 1.);
+    }
+
+    pub fn set_layer_visible(&mut self, index: u32, visible: bool) {
+        self.layer_mut(index).style.visible = visible;
+    }
+
+    pub fn set_layer_blend_mode(&mut self, index: u32, mode: BlendMode) {
+        self.layer_mut(index).style.blend_mode = mode;
+    }
+
+    fn layer_mut(&mut self, index: u32) -> &mut Layer {
+        // the screen's own pixels live in the drawing buffer, its layer only has a style
+        let (width, height) = match index {
+            0 => (0, 0),
+            _ => (self.drawing_buffer.width(), self.drawing_buffer.height()),
+        };
+        self.layers.entry(index).or_insert_with(|| Layer {
+            buffer: Vec2d::new_with(width, height, BLANK),
+            style: LayerStyle::default(),
+        })
+    }
+
+    /// Keeps the layers the same size as the drawing buffer.
+    pub(crate) fn resize_layers(&mut self) {
+        let (width, height) = (self.drawing_buffer.width(), self.drawing_buffer.height());
+        for (_, layer) in self.layers.iter_mut().filter(|(index, _)| **index != 0) {
+            layer.buffer.resize_with(width, height, BLANK);
+        }
+    }
+
+    /// The drawing buffer with every visible layer put on top of it.
+    pub(crate) fn composite_layers(&self) -> Vec2d<Pixel> {
+        let screen = self.layer_style(0);
+        let mut layers = self
+            .layers
+            .iter()
+            .filter(|(index, layer)| {
+                **index != 0 && layer.style.visible && layer.style.opacity > 0.
+            })
+            .peekable();
+        if screen == LayerStyle::default() && layers.peek().is_none() {
+            return self.drawing_buffer.clone();
+        }
+
+        let (width, height) = (self.drawing_buffer.width(), self.drawing_buffer.height());
+        let mut frame = if screen == LayerStyle::default() {
+            self.drawing_buffer.clone()
+        } else {
+            let mut frame = Vec2d::<Pixel>::new(width, height);
+            if screen.visible {
+                let alpha = (screen.opacity * 255.).round() as u8;
+                for y in 0..height {
+                    for x in 0..width {
+                        let pixel = self.drawing_buffer.get(x, y);
+                        let color = Color::rgba(pixel.r, pixel.g, pixel.b, alpha);
+                        frame.set(x, y, screen.blend_mode.blend(Pixel::default(), color));
+                    }
+                }
+            }
+            frame
+        };
+        for (_, layer) in layers {
+            let style = layer.style;
+            for y in 0..height {
+                for x in 0..width {
+                    let mut color = *layer.buffer.get(x, y);
+                    if color.a == 0 {
+                        continue;
+                    }
+                    color.a = (color.a as f32 * style.opacity).round() as u8;
+                    let pixel = frame.get_mut(x, y);
+                    *pixel = style.blend_mode.blend(*pixel, color);
+                }
+            }
+        }
+        frame
+    }
+}
@@ -1,16 +1,17 @@
-#![feature(const_float_methods)]
-
-use crossterm::{
-    event::{KeyboardEnhancementFlags, PushKeyboardEnhancementFlags},
-    *,
-};
+use crossterm::*;
 use enumset::EnumSet;
 use rand::{distributions::uniform::SampleUniform, rngs::ThreadRng, thread_rng, Rng};
-use std::time::{Duration, Instant};
+use std::{
+    collections::{BTreeMap, VecDeque},
+    time::{Duration, Instant},
+};
 
 mod color;
 pub use color::*;
 
+mod paint;
+pub use paint::*;
+
 mod vec2d;
 pub use vec2d::*;
 
@@ -29,8 +30,41 @@ pub use keyboard::*;
 mod math;
 pub use math::*;
 
+mod backend;
+pub use backend::*;
+
+mod blend;
+pub use blend::*;
+
+mod camera;
+pub use camera::*;
+
+mod resolution;
+pub use resolution::*;
+
+mod layer;
+pub use layer::*;
+
+mod window;
+pub use window::*;
+
+mod viewport;
+pub use viewport::*;
+
 mod drawing;
+pub use drawing::*;
+
+mod path;
+pub use path::*;
+
+mod drawable;
+pub use drawable::*;
+
+pub mod testing;
+
+mod clip;
 mod events;
+mod target;
 
 #[derive(Debug, Clone)]
 struct State {
@@ -60,10 +94,23 @@ impl Default for State {
 pub struct Context {
     display_buffer: Vec2d<Pixel>,
     drawing_buffer: Vec2d<Pixel>,
+    targets: Vec<target::Target>,
+    layers: BTreeMap<u32, Layer>,
+    current_layer: u32,
 
     previous_state: State,
     current_state: State,
 
+    backend: Box<dyn Backend>,
+    pending_events: VecDeque<event::Event>,
+
+    blend_mode: BlendMode,
+    clip_stack: Vec<Rectangle>,
+    camera: Option<Camera2D>,
+    origin: Vec2,
+    view_size: Option<Vec2>,
+    windows: Vec<window::SavedView>,
+    virtual_resolution: Option<VirtualResolution>,
     target_fps: f32,
     exit_key_combo: EnumSet<Key>,
     exit_hook: Option<Box<dyn FnOnce(bool)>>,
@@ -78,32 +125,21 @@ impl Default for Context {
 
 impl Drop for Context {
     fn drop(&mut self) {
-        execute!(
-            std::io::stdout(),
-            cursor::Show,
-            style::ResetColor,
-            terminal::LeaveAlternateScreen,
-            event::PopKeyboardEnhancementFlags,
-            event::DisableMouseCapture,
-        )
-        .unwrap();
-        terminal::disable_raw_mode().unwrap();
+        self.backend.restore().unwrap();
     }
 }
 
 impl Context {
     pub fn new() -> Self {
-        terminal::enable_raw_mode().unwrap();
-        execute!(
-            std::io::stdout(),
-            cursor::Hide,
-            terminal::EnterAlternateScreen,
-            PushKeyboardEnhancementFlags(KeyboardEnhancementFlags::all()),
-            event::EnableMouseCapture,
-        )
-        .unwrap();
-
-        let (width, height) = terminal::size().unwrap();
+        Self::with_backend(CrosstermBackend::new())
+    }
+
+    pub fn with_backend<B>(mut backend: B) -> Self
+    where
+        B: Backend + 'static,
+    {
+        backend.init().unwrap();
+        let (width, height) = backend.size().unwrap();
 
         let mut display_buffer = Vec2d::new(width as usize, height as usize * 2);
         let drawing_buffer = display_buffer.clone();
@@ -112,9 +148,21 @@ impl Context {
         let mut ctx = Self {
             display_buffer,
             drawing_buffer,
+            targets: Vec::new(),
+            layers: BTreeMap::new(),
+            current_layer: 0,
 
             previous_state: State::default(),
             current_state: State::default(),
+            backend: Box::new(backend),
+            pending_events: VecDeque::new(),
+            blend_mode: BlendMode::default(),
+            clip_stack: Vec::new(),
+            camera: None,
+            origin: Vec2::ZERO,
+            view_size: None,
+            windows: Vec::new(),
+            virtual_resolution: None,
             target_fps: f32::MAX,
             exit_key_combo: Key::LeftControl | Key::C,
             exit_hook: None,
@@ -124,6 +172,11 @@ impl Context {
         ctx
     }
 
+    /// A context that doesn't touch the terminal, `width` and `height` are in cells.
+    pub fn headless(width: u16, height: u16) -> Self {
+        Self::with_backend(HeadlessBackend::new(width, height))
+    }
+
     pub fn width(&self) -> f32 {
         self.display_buffer.width() as f32
     }
@@ -167,12 +220,21 @@ impl Context {
         self.target_fps = fps;
     }
 
+    /// Time the last frame took, in seconds.
+    pub fn get_frame_time(&self) -> f32 {
+        (self.current_state.time - self.previous_state.time).as_secs_f32()
+    }
+
+    /// Width of whatever is being drawn to: the screen, a sprite (see [`Context::draw_to_sprite`])
+    /// or a window (see [`Context::draw_window`]).
     pub fn screen_width(&self) -> f32 {
-        self.drawing_buffer.width() as f32
+        self.view_size
+            .map_or(self.canvas().width() as f32, |size| size.x)
     }
 
     pub fn screen_height(&self) -> f32 {
-        self.drawing_buffer.height() as f32
+        self.view_size
+            .map_or(self.canvas().height() as f32, |size| size.y)
     }
 
     pub fn set_exit_hook<F>(&mut self, hook: F)
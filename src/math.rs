@@ -6,3 +6,15 @@ pub use vec2::*;
 
 mod rectangle;
 pub use rectangle::*;
+
+mod circle;
+pub use circle::*;
+
+mod triangle;
+pub use triangle::*;
+
+mod line_segment;
+pub use line_segment::*;
+
+mod transform;
+pub use transform::*;
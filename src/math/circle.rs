@@ -0,0 +1,28 @@
+use crate::Vec2;
+
+#[macro_export]
+macro_rules! circle {
+    ($x:expr, $y:expr, $r:expr) => {
+        $crate::Circle::new($crate::vec2!($x, $y), $r as f32)
+    };
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Default)]
+pub struct Circle {
+    pub center: Vec2,
+    pub radius: f32,
+}
+
+impl Circle {
+    pub const fn new(center: Vec2, radius: f32) -> Self {
+        Self { center, radius }
+    }
+
+    pub fn contains(&self, point: &Vec2) -> bool {
+        self.center.distance(point) < self.radius
+    }
+
+    pub fn is_colliding_with(&self, other: &Self) -> bool {
+        self.center.distance(&other.center) < self.radius + other.radius
+    }
+}
@@ -0,0 +1,21 @@
+use crate::Vec2;
+
+#[derive(Debug, Clone, Copy, PartialEq, Default)]
+pub struct LineSegment {
+    pub start: Vec2,
+    pub end: Vec2,
+}
+
+impl LineSegment {
+    pub const fn new(start: Vec2, end: Vec2) -> Self {
+        Self { start, end }
+    }
+
+    pub fn length(&self) -> f32 {
+        self.start.distance(&self.end)
+    }
+
+    pub fn midpoint(&self) -> Vec2 {
+        self.start.midpoint(&self.end)
+    }
+}
@@ -1,4 +1,4 @@
-use std::{fmt, ops::*};
+use crate::Vec2;
 
 #[macro_export]
 macro_rules! rect {
@@ -26,4 +26,20 @@ impl Rectangle {
             && self.y < other.y + other.h
             && self.y + self.h > other.y
     }
+
+    /// The area shared by both rectangles, `None` if they don't overlap.
+    pub fn intersection(&self, other: &Self) -> Option<Self> {
+        let x = self.x.max(other.x);
+        let y = self.y.max(other.y);
+        let w = (self.x + self.w).min(other.x + other.w) - x;
+        let h = (self.y + self.h).min(other.y + other.h) - y;
+        (w > 0. && h > 0.).then(|| Self::new(x, y, w, h))
+    }
+
+    pub fn contains(&self, point: &Vec2) -> bool {
+        point.x >= self.x
+            && point.x < self.x + self.w
+            && point.y >= self.y
+            && point.y < self.y + self.h
+    }
 }
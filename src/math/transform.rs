@@ -0,0 +1,66 @@
+use crate::Vec2;
+use std::f32::consts::TAU;
+
+/// Scales, then rotates (in radians), then translates points.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct Transform {
+    pub translation: Vec2,
+    pub rotation: f32,
+    pub scale: f32,
+}
+
+impl Default for Transform {
+    fn default() -> Self {
+        Self::IDENTITY
+    }
+}
+
+impl Transform {
+    pub const IDENTITY: Self = Self::new(Vec2::ZERO, 0., 1.);
+
+    pub const fn new(translation: Vec2, rotation: f32, scale: f32) -> Self {
+        Self {
+            translation,
+            rotation,
+            scale,
+        }
+    }
+
+    pub const fn translation(translation: Vec2) -> Self {
+        Self::new(translation, 0., 1.)
+    }
+
+    pub fn apply(&self, point: Vec2) -> Vec2 {
+        (point * self.scale).rotate(self.rotation) + self.translation
+    }
+
+    /// Applies the transform to a direction, ignoring the translation.
+    pub fn apply_vector(&self, vector: Vec2) -> Vec2 {
+        (vector * self.scale).rotate(self.rotation)
+    }
+
+    pub fn inverse(&self) -> Self {
+        Self::new(
+            (-self.translation / self.scale).rotate(-self.rotation),
+            -self.rotation,
+            1. / self.scale,
+        )
+    }
+
+    /// A transform doing `self` first, then `next`.
+    pub fn then(&self, next: &Self) -> Self {
+        Self::new(
+            next.apply(self.translation),
+            self.rotation + next.rotation,
+            self.scale * next.scale,
+        )
+    }
+
+    pub fn has_rotation(&self) -> bool {
+        self.rotation.rem_euclid(TAU) != 0.
+    }
+
+    pub fn is_identity(&self) -> bool {
+        self.translation == Vec2::ZERO && !self.has_rotation() && self.scale == 1.
+    }
+}
@@ -0,0 +1,35 @@
+use crate::Vec2;
+
+#[derive(Debug, Clone, Copy, PartialEq, Default)]
+pub struct Triangle {
+    pub a: Vec2,
+    pub b: Vec2,
+    pub c: Vec2,
+}
+
+impl Triangle {
+    pub const fn new(a: Vec2, b: Vec2, c: Vec2) -> Self {
+        Self { a, b, c }
+    }
+
+    pub fn points(&self) -> [Vec2; 3] {
+        [self.a, self.b, self.c]
+    }
+
+    pub fn area(&self) -> f32 {
+        let (ab, ac) = (self.b - self.a, self.c - self.a);
+        (ab.x * ac.y - ab.y * ac.x).abs() / 2.
+    }
+
+    pub fn contains(&self, point: &Vec2) -> bool {
+        let side = |p: Vec2, q: Vec2| (q.x - p.x) * (point.y - p.y) - (q.y - p.y) * (point.x - p.x);
+        let (d1, d2, d3) = (
+            side(self.a, self.b),
+            side(self.b, self.c),
+            side(self.c, self.a),
+        );
+        let has_neg = d1 < 0. || d2 < 0. || d3 < 0.;
+        let has_pos = d1 > 0. || d2 > 0. || d3 > 0.;
+        !(has_neg && has_pos)
+    }
+}
@@ -394,6 +394,20 @@ impl_ops! {
     Div div DivAssign div_assign |a, b| (a.x / b.x, a.y / b.y);
 }
 
+impl Neg for Vec2 {
+    type Output = Vec2;
+    fn neg(self) -> Vec2 {
+        Vec2::new(-self.x, -self.y)
+    }
+}
+
+impl Neg for &Vec2 {
+    type Output = Vec2;
+    fn neg(self) -> Vec2 {
+        Vec2::new(-self.x, -self.y)
+    }
+}
+
 impl From<[f32; 2]> for Vec2 {
     fn from(v: [f32; 2]) -> Self {
         Self::new(v[0], v[1])
@@ -30,8 +30,8 @@ impl Context {
                 self.current_state.mouse_buttons.remove(btn.into());
             }
             MouseEventKind::Moved => {
-                self.current_state.mouse_position.0 = event.column as f32;
-                self.current_state.mouse_position.1 = event.row as f32 * 2.;
+                self.current_state.mouse_position =
+                    self.display_to_screen(event.column as f32, event.row as f32 * 2.);
                 self.current_state
                     .mouse_positions
                     .push(self.current_state.mouse_position);
@@ -39,8 +39,8 @@ impl Context {
             MouseEventKind::Drag(btn) => {
                 self.current_state.mouse_buttons.insert(btn.into());
 
-                self.current_state.mouse_position.0 = event.column as f32;
-                self.current_state.mouse_position.1 = event.row as f32 * 2.;
+                self.current_state.mouse_position =
+                    self.display_to_screen(event.column as f32, event.row as f32 * 2.);
                 self.current_state
                     .mouse_positions
                     .push(self.current_state.mouse_position);
@@ -65,8 +65,10 @@ impl Context {
             && self.previous_state.mouse_buttons.contains(btn)
     }
 
+    /// Relative to the current window, if drawing in one.
     pub fn mouse_position(&self) -> (f32, f32) {
-        self.current_state.mouse_position
+        let (x, y) = self.current_state.mouse_position;
+        (x - self.origin.x, y - self.origin.y)
     }
 
     pub fn mouse_positions(&self) -> &[(f32, f32)] {
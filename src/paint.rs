@@ -0,0 +1,165 @@
+use crate::{Color, Transform, Vec2};
+use std::f32::consts::TAU;
+
+/// What fill primitives fill with, a plain [`Color`] converts into one.
+#[derive(Debug, Clone, PartialEq)]
+pub enum Paint {
+    Solid(Color),
+    /// Goes from the first stop at `start` to the last one at `end`.
+    Linear {
+        start: Vec2,
+        end: Vec2,
+        gradient: Gradient,
+    },
+    /// Goes from the first stop at `center` to the last one `radius` away.
+    Radial {
+        center: Vec2,
+        radius: f32,
+        gradient: Gradient,
+    },
+    /// Goes clockwise around `center`, starting at `angle` (in radians).
+    Conic {
+        center: Vec2,
+        angle: f32,
+        gradient: Gradient,
+    },
+}
+
+/// Color stops, each an offset between 0 and 1 with its color.
+#[derive(Debug, Clone, PartialEq)]
+pub struct Gradient {
+    stops: Vec<(f32, Color)>,
+}
+
+impl Gradient {
+    pub fn new(stops: &[(f32, Color)]) -> Self {
+        let mut stops = stops.to_vec();
+        stops.sort_by(|a, b| a.0.total_cmp(&b.0));
+        Self { stops }
+    }
+
+    pub fn stops(&self) -> &[(f32, Color)] {
+        &self.stops
+    }
+
+    pub fn color_at(&self, t: f32) -> Color {
+        let Some(first) = self.stops.first() else {
+            return Color::rgba(0, 0, 0, 0);
+        };
+        let t = if t.is_nan() { 0. } else { t };
+        if t <= first.0 {
+            return first.1;
+        }
+        for pair in self.stops.windows(2) {
+            let ((from, a), (to, b)) = (pair[0], pair[1]);
+            if t <= to {
+                let f = if to > from {
+                    (t - from) / (to - from)
+                } else {
+                    1.
+                };
+                let mix = |a: u8, b: u8| (a as f32 + (b as f32 - a as f32) * f).round() as u8;
+                return Color::rgba(mix(a.r, b.r), mix(a.g, b.g), mix(a.b, b.b), mix(a.a, b.a));
+            }
+        }
+        self.stops[self.stops.len() - 1].1
+    }
+}
+
+impl Paint {
+    pub fn linear(start: Vec2, end: Vec2, stops: &[(f32, Color)]) -> Self {
+        Paint::Linear {
+            start,
+            end,
+            gradient: Gradient::new(stops),
+        }
+    }
+
+    pub fn radial(center: Vec2, radius: f32, stops: &[(f32, Color)]) -> Self {
+        Paint::Radial {
+            center,
+            radius,
+            gradient: Gradient::new(stops),
+        }
+    }
+
+    pub fn conic(center: Vec2, angle: f32, stops: &[(f32, Color)]) -> Self {
+        Paint::Conic {
+            center,
+            angle,
+            gradient: Gradient::new(stops),
+        }
+    }
+
+    /// The same paint, with its geometry moved by `transform`.
+    pub fn transformed(&self, transform: &Transform) -> Self {
+        match self.clone() {
+            Paint::Solid(color) => Paint::Solid(color),
+            Paint::Linear {
+                start,
+                end,
+                gradient,
+            } => Paint::Linear {
+                start: transform.apply(start),
+                end: transform.apply(end),
+                gradient,
+            },
+            Paint::Radial {
+                center,
+                radius,
+                gradient,
+            } => Paint::Radial {
+                center: transform.apply(center),
+                radius: radius * transform.scale,
+                gradient,
+            },
+            Paint::Conic {
+                center,
+                angle,
+                gradient,
+            } => Paint::Conic {
+                center: transform.apply(center),
+                angle: angle + transform.rotation,
+                gradient,
+            },
+        }
+    }
+
+    pub fn color_at(&self, x: f32, y: f32) -> Color {
+        let p = Vec2::new(x, y);
+        match self {
+            Paint::Solid(color) => *color,
+            Paint::Linear {
+                start,
+                end,
+                gradient,
+            } => {
+                let axis = end - start;
+                let t = (p - start).dot(&axis) / axis.dot(&axis);
+                gradient.color_at(t)
+            }
+            Paint::Radial {
+                center,
+                radius,
+                gradient,
+            } => gradient.color_at(p.distance(center) / radius),
+            Paint::Conic {
+                center,
+                angle,
+                gradient,
+            } => gradient.color_at((p.angle_from(center) - angle).rem_euclid(TAU) / TAU),
+        }
+    }
+}
+
+impl From<Color> for Paint {
+    fn from(color: Color) -> Self {
+        Paint::Solid(color)
+    }
+}
+
+impl From<&Color> for Paint {
+    fn from(color: &Color) -> Self {
+        Paint::Solid(*color)
+    }
+}
@@ -0,0 +1,214 @@
+use crate::{Color, Context, FillRule, Paint, Transform, Vec2};
+
+/// How far (in pixels) flattened curves are allowed to stray from the real curve.
+const TOLERANCE: f32 = 0.25;
+const MAX_DEPTH: u32 = 16;
+
+#[derive(Debug, Clone, Copy, PartialEq)]
+enum Command {
+    MoveTo(Vec2),
+    LineTo(Vec2),
+    QuadTo(Vec2, Vec2),
+    CubicTo(Vec2, Vec2, Vec2),
+    Close,
+}
+
+/// A shape made of lines and curves, drawn with [`Context::fill_path`] or [`Context::stroke_path`].
+#[derive(Debug, Clone, Default)]
+pub struct Path {
+    commands: Vec<Command>,
+    fill_rule: FillRule,
+}
+
+impl Path {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Starts a new subpath at `point`.
+    pub fn move_to(&mut self, point: Vec2) -> &mut Self {
+        self.commands.push(Command::MoveTo(point));
+        self
+    }
+
+    pub fn line_to(&mut self, point: Vec2) -> &mut Self {
+        self.commands.push(Command::LineTo(point));
+        self
+    }
+
+    /// Quadratic bezier curve to `point`.
+    pub fn quad_to(&mut self, control: Vec2, point: Vec2) -> &mut Self {
+        self.commands.push(Command::QuadTo(control, point));
+        self
+    }
+
+    /// Cubic bezier curve to `point`.
+    pub fn cubic_to(&mut self, control1: Vec2, control2: Vec2, point: Vec2) -> &mut Self {
+        self.commands
+            .push(Command::CubicTo(control1, control2, point));
+        self
+    }
+
+    /// Connects the current subpath back to its start.
+    pub fn close(&mut self) -> &mut Self {
+        self.commands.push(Command::Close);
+        self
+    }
+
+    pub fn set_fill_rule(&mut self, rule: FillRule) -> &mut Self {
+        self.fill_rule = rule;
+        self
+    }
+
+    pub fn fill_rule(&self) -> FillRule {
+        self.fill_rule
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.commands.is_empty()
+    }
+
+    /// Turns the path into polylines, each with whether it's closed.
+    /// Subpaths that are a single point are left out.
+    pub fn flatten(&self, tolerance: f32) -> Vec<(Vec<Vec2>, bool)> {
+        let mut polylines = Vec::new();
+        let mut current = Vec::<Vec2>::new();
+        let mut finish = |current: &mut Vec<Vec2>, closed: bool| {
+            let points = std::mem::take(current);
+            if points.len() > 1 {
+                polylines.push((points, closed));
+            }
+        };
+
+        let mut last = Vec2::default();
+        for command in &self.commands {
+            match *command {
+                Command::MoveTo(p) => {
+                    finish(&mut current, false);
+                    current.push(p);
+                }
+                Command::LineTo(p) => {
+                    if current.is_empty() {
+                        current.push(last);
+                    }
+                    current.push(p);
+                }
+                Command::QuadTo(c, p) => {
+                    if current.is_empty() {
+                        current.push(last);
+                    }
+                    flatten_quad(last, c, p, tolerance, 0, &mut current);
+                }
+                Command::CubicTo(c1, c2, p) => {
+                    if current.is_empty() {
+                        current.push(last);
+                    }
+                    flatten_cubic(last, c1, c2, p, tolerance, 0, &mut current);
+                }
+                Command::Close => {
+                    // drawing after `close` continues from where the subpath started
+                    last = current.first().copied().unwrap_or(last);
+                    finish(&mut current, true);
+                    continue;
+                }
+            }
+            last = current.last().copied().unwrap_or(last);
+        }
+        finish(&mut current, false);
+        polylines
+    }
+}
+
+/// Flattens `path` finely enough for how much `transform` scales it, then transforms it.
+fn flatten_transformed(path: &Path, transform: &Transform) -> Vec<(Vec<Vec2>, bool)> {
+    let mut polylines = path.flatten(TOLERANCE / transform.scale);
+    for (points, _) in &mut polylines {
+        for p in points {
+            *p = transform.apply(*p);
+        }
+    }
+    polylines
+}
+
+fn flatten_quad(p0: Vec2, p1: Vec2, p2: Vec2, tolerance: f32, depth: u32, out: &mut Vec<Vec2>) {
+    let deviation = (p0 - p1 * 2. + p2).length() / 4.;
+    if deviation <= tolerance || depth >= MAX_DEPTH {
+        out.push(p2);
+        return;
+    }
+    let (a, b) = (p0.midpoint(&p1), p1.midpoint(&p2));
+    let mid = a.midpoint(&b);
+    flatten_quad(p0, a, mid, tolerance, depth + 1, out);
+    flatten_quad(mid, b, p2, tolerance, depth + 1, out);
+}
+
+fn flatten_cubic(
+    p0: Vec2,
+    p1: Vec2,
+    p2: Vec2,
+    p3: Vec2,
+    tolerance: f32,
+    depth: u32,
+    out: &mut Vec<Vec2>,
+) {
+    let deviation = (p0 - p1 * 2. + p2)
+        .length()
+        .max((p1 - p2 * 2. + p3).length())
+        * 0.75;
+    if deviation <= tolerance || depth >= MAX_DEPTH {
+        out.push(p3);
+        return;
+    }
+    let (a, b, c) = (p0.midpoint(&p1), p1.midpoint(&p2), p2.midpoint(&p3));
+    let (d, e) = (a.midpoint(&b), b.midpoint(&c));
+    let mid = d.midpoint(&e);
+    flatten_cubic(p0, a, d, mid, tolerance, depth + 1, out);
+    flatten_cubic(mid, e, c, p3, tolerance, depth + 1, out);
+}
+
+impl Context {
+    /// Fills every subpath of `path` as one shape, open subpaths are closed implicitly.
+    pub fn fill_path<P>(&mut self, path: &Path, paint: P)
+    where
+        P: Into<Paint>,
+    {
+        let transform = self.transform().unwrap_or_default();
+        let paint = paint.into().transformed(&transform);
+        let contours = flatten_transformed(path, &transform)
+            .into_iter()
+            .map(|(points, _)| points)
+            .collect::<Vec<_>>();
+        self.untransformed(|ctx| ctx.fill_contours(&contours, path.fill_rule(), &paint));
+    }
+
+    pub fn stroke_path(&mut self, path: &Path, thickness: f32, color: Color) {
+        let transform = self.transform().unwrap_or_default();
+        let thickness = (thickness * transform.scale).max(1.);
+        let polylines = flatten_transformed(path, &transform);
+        self.untransformed(|ctx| {
+            for (points, closed) in polylines {
+                ctx.stroke_polyline(&points, closed, thickness, color);
+            }
+        });
+    }
+
+    pub fn draw_bezier_quad(&mut self, p0: Vec2, p1: Vec2, p2: Vec2, thickness: f32, color: Color) {
+        let mut path = Path::new();
+        path.move_to(p0).quad_to(p1, p2);
+        self.stroke_path(&path, thickness, color);
+    }
+
+    pub fn draw_bezier_cubic(
+        &mut self,
+        p0: Vec2,
+        p1: Vec2,
+        p2: Vec2,
+        p3: Vec2,
+        thickness: f32,
+        color: Color,
+    ) {
+        let mut path = Path::new();
+        path.move_to(p0).cubic_to(p1, p2, p3);
+        self.stroke_path(&path, thickness, color);
+    }
+}
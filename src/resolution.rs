@@ -0,0 +1,109 @@
+use crate::{Context, Pixel, Vec2d};
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub enum ScaleMode {
+    /// Fills as much of the terminal as possible, some pixels may end up bigger than others.
+    #[default]
+    Nearest,
+    /// Only scales by whole numbers so every pixel is the same size.
+    Integer,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub(crate) struct VirtualResolution {
+    width: usize,
+    height: usize,
+    mode: ScaleMode,
+}
+
+/// Where the canvas ends up in the display, `scale` display pixels per canvas pixel.
+#[derive(Debug, Clone, Copy, PartialEq)]
+struct Letterbox {
+    x: f32,
+    y: f32,
+    scale: f32,
+}
+
+impl Context {
+    /// Draws into a fixed `width` x `height` canvas that's scaled to fit the terminal,
+    /// with black bars filling whatever is left.
+    pub fn set_virtual_resolution(&mut self, width: f32, height: f32, mode: ScaleMode) {
+        let resolution = VirtualResolution {
+            width: width as usize,
+            height: height as usize,
+            mode,
+        };
+        self.drawing_buffer
+            .resize(resolution.width, resolution.height);
+        self.virtual_resolution = Some(resolution);
+        self.resize_layers();
+        self.force_redraw();
+    }
+
+    /// Goes back to drawing straight into the terminal.
+    pub fn clear_virtual_resolution(&mut self) {
+        self.virtual_resolution = None;
+        self.drawing_buffer
+            .resize(self.display_buffer.width(), self.display_buffer.height());
+        self.resize_layers();
+        self.force_redraw();
+    }
+
+    /// Scales the drawing buffer into a buffer the size of the display.
+    pub(crate) fn scale_to_display(&self, frame: Vec2d<Pixel>) -> Vec2d<Pixel> {
+        let Some(letterbox) = self.letterbox() else {
+            return frame;
+        };
+        let mut scaled = Vec2d::new(self.display_buffer.width(), self.display_buffer.height());
+        for y in 0..scaled.height() {
+            for x in 0..scaled.width() {
+                let (fx, fy) = letterbox.canvas_position(x as f32, y as f32);
+                if fx >= 0.
+                    && fy >= 0.
+                    && (fx as usize) < frame.width()
+                    && (fy as usize) < frame.height()
+                {
+                    scaled.set(x, y, *frame.get(fx as usize, fy as usize));
+                }
+            }
+        }
+        scaled
+    }
+
+    /// Maps a position in display pixels to the drawing buffer.
+    pub(crate) fn display_to_screen(&self, x: f32, y: f32) -> (f32, f32) {
+        match self.letterbox() {
+            Some(letterbox) => {
+                let (x, y) = letterbox.canvas_position(x, y);
+                (x.floor(), y.floor())
+            }
+            None => (x, y),
+        }
+    }
+
+    fn letterbox(&self) -> Option<Letterbox> {
+        let resolution = self.virtual_resolution?;
+        let (width, height) = (resolution.width as f32, resolution.height as f32);
+        let display_width = self.display_buffer.width() as f32;
+        let display_height = self.display_buffer.height() as f32;
+        let mut scale = (display_width / width).min(display_height / height);
+        if resolution.mode == ScaleMode::Integer && scale >= 1. {
+            scale = scale.floor();
+        }
+        Some(Letterbox {
+            x: ((display_width - width * scale) / 2.).floor(),
+            y: ((display_height - height * scale) / 2.).floor(),
+            scale,
+        })
+    }
+
+    fn force_redraw(&mut self) {
+        self.display_buffer.fill(Pixel { r: 1, g: 2, b: 3 });
+    }
+}
+
+impl Letterbox {
+    fn canvas_position(&self, x: f32, y: f32) -> (f32, f32) {
+        ((x - self.x) / self.scale, (y - self.y) / self.scale)
+    }
+}
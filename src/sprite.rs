@@ -1,4 +1,22 @@
-use crate::{ctx, Color, Context, Vec2d};
+use crate::{
+    ctx, drawing::rect_points, target::Target, Color, Context, Rectangle, Transform, Vec2, Vec2d,
+    BLANK,
+};
+
+mod animated;
+pub use animated::*;
+
+mod file;
+pub use file::*;
+
+mod sheet;
+pub use sheet::*;
+
+mod text;
+pub use text::*;
+
+mod transformed;
+pub use transformed::*;
 
 pub struct Sprite {
     data: Vec2d<Color>,
@@ -6,8 +24,8 @@ pub struct Sprite {
 
 impl Sprite {
     pub fn new(width: f32, height: f32) -> Self {
-        let mut data = Vec2d::new_with(width as usize, height as usize, Color::rgba(0, 0, 0, 0));
-        data.fill(Color::rgba(0, 0, 0, 0));
+        let mut data = Vec2d::new_with(width as usize, height as usize, BLANK);
+        data.fill(BLANK);
         Self { data }
     }
 
@@ -15,7 +33,12 @@ impl Sprite {
         self.data.set(x as usize, y as usize, color);
     }
 
+    /// [`BLANK`] outside of the sprite.
     pub fn get_pixel(&self, x: f32, y: f32) -> Color {
+        let (x, y) = (x.floor(), y.floor());
+        if !(x >= 0. && x < self.width() && y >= 0. && y < self.height()) {
+            return BLANK;
+        }
         *self.data.get(x as usize, y as usize)
     }
 
@@ -29,7 +52,7 @@ impl Sprite {
 
     pub fn resize(&mut self, width: f32, height: f32) {
         self.data
-            .resize_with(width as usize, height as usize, Color::rgba(0, 0, 0, 0));
+            .resize_with(width as usize, height as usize, BLANK);
     }
 
     pub fn draw(&self, x: f32, y: f32) {
@@ -37,21 +60,113 @@ impl Sprite {
     }
 
     pub fn draw_with_ctx(&self, ctx: &mut Context, x: f32, y: f32) {
-        for local_x in 0..self.width() as usize {
-            let local_x = local_x as f32;
-            let x = x + local_x;
+        self.draw_region_with_ctx(ctx, self.bounds(), Vec2::new(x, y));
+    }
+
+    /// The whole sprite, as a region to draw.
+    pub fn bounds(&self) -> Rectangle {
+        Rectangle::new(0., 0., self.width(), self.height())
+    }
+
+    pub fn draw_region(&self, src: Rectangle, dest: Vec2) {
+        self.draw_region_with_ctx(ctx(), src, dest);
+    }
+
+    /// Draws the pixels of the sprite inside `src` with the top left corner of `src` at `dest`.
+    pub fn draw_region_with_ctx(&self, ctx: &mut Context, src: Rectangle, dest: Vec2) {
+        let src = Rectangle::new(src.x.floor(), src.y.floor(), src.w.trunc(), src.h.trunc());
+        if let Some(t) = ctx.transform() {
+            let transform = Transform::translation(dest).then(&t);
+            return ctx.untransformed(|ctx| self.draw_transformed(ctx, src, &transform));
+        }
+        let Some(visible) = src.intersection(&self.bounds()) else {
+            return;
+        };
+        for local_x in visible.x as usize..(visible.x + visible.w) as usize {
+            let x = dest.x + local_x as f32 - src.x;
             if x >= ctx.screen_width() {
                 break;
             }
-            for local_y in 0..self.height() as usize {
-                let local_y = local_y as f32;
-                let y = y + local_y;
+            for local_y in visible.y as usize..(visible.y + visible.h) as usize {
+                let y = dest.y + local_y as f32 - src.y;
                 if y >= ctx.screen_height() {
                     break;
                 }
-                let color = self.get_pixel(local_x, local_y);
+                let color = *self.data.get(local_x, local_y);
                 ctx.set_pixel(x, y, color);
             }
         }
     }
+
+    /// Draws the `src` region of the sprite with `transform` taking it to the screen
+    /// (the top left corner of `src` being the origin).
+    /// Every screen pixel it covers is mapped back into the sprite, so there are no holes.
+    pub(crate) fn draw_transformed(
+        &self,
+        ctx: &mut Context,
+        src: Rectangle,
+        transform: &Transform,
+    ) {
+        let Some(visible) = src.intersection(&self.bounds()) else {
+            return;
+        };
+        let corners = rect_points(visible.x - src.x, visible.y - src.y, visible.w, visible.h)
+            .map(|p| transform.apply(p));
+        let (min, max) = corners
+            .iter()
+            .fold((Vec2::MAX, Vec2::MIN), |(min, max), p| {
+                (min.min(p), max.max(p))
+            });
+        let x_start = min.x.floor().max(0.) as usize;
+        let y_start = min.y.floor().max(0.) as usize;
+        let x_end = max.x.ceil().clamp(0., ctx.screen_width()) as usize;
+        let y_end = max.y.ceil().clamp(0., ctx.screen_height()) as usize;
+
+        let inverse = transform.inverse();
+        for my in y_start..y_end {
+            for mx in x_start..x_end {
+                let local = inverse.apply(Vec2::new(mx as f32 + 0.5, my as f32 + 0.5))
+                    + Vec2::new(src.x, src.y);
+                if !visible.contains(&local) {
+                    continue;
+                }
+                let color = self.get_pixel(local.x, local.y);
+                ctx.set_pixel(mx as f32, my as f32, color);
+            }
+        }
+    }
+}
+
+impl Context {
+    /// Draws everything in `f` into `sprite` instead of the screen, so it can be drawn again later.
+    /// The camera, clipping rectangles and current window are put aside until `f` returns.
+    pub fn draw_to_sprite<F, R>(&mut self, sprite: &mut Sprite, f: F) -> R
+    where
+        F: FnOnce(&mut Self) -> R,
+    {
+        self.begin_sprite_target(sprite);
+        let result = f(self);
+        self.end_sprite_target(sprite);
+        result
+    }
+
+    pub(crate) fn begin_sprite_target(&mut self, sprite: &mut Sprite) {
+        let data = std::mem::replace(&mut sprite.data, Vec2d::new_with(0, 0, BLANK));
+        self.targets.push(Target {
+            data,
+            camera: self.camera.take(),
+            clip_stack: std::mem::take(&mut self.clip_stack),
+            origin: std::mem::take(&mut self.origin),
+            view_size: self.view_size.take(),
+        });
+    }
+
+    pub(crate) fn end_sprite_target(&mut self, sprite: &mut Sprite) {
+        let target = self.targets.pop().unwrap();
+        sprite.data = target.data;
+        self.camera = target.camera;
+        self.clip_stack = target.clip_stack;
+        self.origin = target.origin;
+        self.view_size = target.view_size;
+    }
 }
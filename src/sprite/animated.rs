@@ -0,0 +1,205 @@
+use super::SpriteSheet;
+use crate::{ctx, Context};
+use std::rc::Rc;
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub enum PlaybackMode {
+    /// Goes back to the first frame after the last one.
+    #[default]
+    Loop,
+    /// Plays forwards then backwards, over and over.
+    PingPong,
+    /// Stops on the last frame.
+    Once,
+}
+
+/// What happened while [`AnimatedSprite::update`] advanced the animation.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum AnimationEvent {
+    /// A looping or ping-pong animation went through all of its frames and started over.
+    Looped,
+    /// A [`PlaybackMode::Once`] animation reached the end of its last frame.
+    Finished,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct Frame {
+    /// Index of the region in the sprite sheet.
+    pub region: usize,
+    /// In seconds.
+    pub duration: f32,
+}
+
+/// Frames of a [`SpriteSheet`] shown one after the other.
+pub struct AnimatedSprite {
+    sheet: Rc<SpriteSheet>,
+    frames: Vec<Frame>,
+    mode: PlaybackMode,
+    speed: f32,
+    current: usize,
+    elapsed: f32,
+    backwards: bool,
+    playing: bool,
+    finished: bool,
+}
+
+impl AnimatedSprite {
+    /// Shows `regions` of `sheet` in order, each for `frame_duration` seconds.
+    pub fn new(sheet: Rc<SpriteSheet>, regions: &[usize], frame_duration: f32) -> Self {
+        let frames = regions
+            .iter()
+            .map(|&region| Frame {
+                region,
+                duration: frame_duration,
+            })
+            .collect();
+        Self::with_frames(sheet, frames)
+    }
+
+    pub fn with_frames(sheet: Rc<SpriteSheet>, frames: Vec<Frame>) -> Self {
+        Self {
+            sheet,
+            frames,
+            mode: PlaybackMode::default(),
+            speed: 1.,
+            current: 0,
+            elapsed: 0.,
+            backwards: false,
+            playing: true,
+            finished: false,
+        }
+    }
+
+    pub fn set_mode(&mut self, mode: PlaybackMode) {
+        self.mode = mode;
+    }
+
+    pub fn mode(&self) -> PlaybackMode {
+        self.mode
+    }
+
+    /// How fast time passes for the animation, 2 plays it twice as fast.
+    pub fn set_speed(&mut self, speed: f32) {
+        self.speed = speed.max(0.);
+    }
+
+    pub fn speed(&self) -> f32 {
+        self.speed
+    }
+
+    pub fn play(&mut self) {
+        self.playing = true;
+    }
+
+    pub fn pause(&mut self) {
+        self.playing = false;
+    }
+
+    /// Goes back to the first frame and plays from there.
+    pub fn restart(&mut self) {
+        self.current = 0;
+        self.elapsed = 0.;
+        self.backwards = false;
+        self.playing = true;
+        self.finished = false;
+    }
+
+    pub fn is_playing(&self) -> bool {
+        self.playing
+    }
+
+    /// Whether a [`PlaybackMode::Once`] animation has reached its end.
+    pub fn is_finished(&self) -> bool {
+        self.finished
+    }
+
+    /// Index of the frame being shown.
+    pub fn current_frame(&self) -> usize {
+        self.current
+    }
+
+    pub fn frames(&self) -> &[Frame] {
+        &self.frames
+    }
+
+    pub fn sheet(&self) -> &SpriteSheet {
+        &self.sheet
+    }
+
+    /// Moves the animation `dt` seconds forward, usually [`Context::get_frame_time`].
+    /// If it looped or finished along the way, the last thing that happened is returned.
+    pub fn update(&mut self, dt: f32) -> Option<AnimationEvent> {
+        let total = self.frames.iter().map(|frame| frame.duration).sum::<f32>();
+        if !self.playing || total <= 0. {
+            return None;
+        }
+        self.elapsed += dt * self.speed;
+        let mut event = None;
+        // skip whole cycles at once, they end up back on the same frame
+        let cycle = match self.mode {
+            PlaybackMode::Loop => total,
+            PlaybackMode::PingPong => match self.frames.as_slice() {
+                [_] => total,
+                [first, .., last] => total * 2. - first.duration - last.duration,
+                [] => unreachable!(),
+            },
+            PlaybackMode::Once => f32::INFINITY,
+        };
+        if self.elapsed >= cycle && cycle > 0. {
+            self.elapsed %= cycle;
+            event = Some(AnimationEvent::Looped);
+        }
+        while self.playing && self.elapsed >= self.frames[self.current].duration {
+            self.elapsed -= self.frames[self.current].duration;
+            if let Some(e) = self.advance() {
+                event = Some(e);
+            }
+        }
+        event
+    }
+
+    fn advance(&mut self) -> Option<AnimationEvent> {
+        let last = self.frames.len() - 1;
+        match self.mode {
+            PlaybackMode::Loop if self.current == last => {
+                self.current = 0;
+                Some(AnimationEvent::Looped)
+            }
+            PlaybackMode::Once if self.current == last => {
+                self.elapsed = 0.;
+                self.playing = false;
+                self.finished = true;
+                Some(AnimationEvent::Finished)
+            }
+            PlaybackMode::PingPong if last == 0 => Some(AnimationEvent::Looped),
+            PlaybackMode::PingPong if self.backwards => {
+                self.current -= 1;
+                if self.current == 0 {
+                    self.backwards = false;
+                    return Some(AnimationEvent::Looped);
+                }
+                None
+            }
+            PlaybackMode::PingPong if self.current == last => {
+                self.current -= 1;
+                // with 2 frames turning around already gets back to the first one
+                self.backwards = self.current > 0;
+                (self.current == 0).then_some(AnimationEvent::Looped)
+            }
+            _ => {
+                self.current += 1;
+                None
+            }
+        }
+    }
+
+    pub fn draw(&self, x: f32, y: f32) {
+        self.draw_with_ctx(ctx(), x, y);
+    }
+
+    pub fn draw_with_ctx(&self, ctx: &mut Context, x: f32, y: f32) {
+        if let Some(frame) = self.frames.get(self.current) {
+            self.sheet.draw_with_ctx(ctx, frame.region, x, y);
+        }
+    }
+}
@@ -0,0 +1,420 @@
+use super::Sprite;
+use crate::{Color, Vec2d, BLANK};
+use std::{error, fmt, fs, io, path::Path};
+
+/// Image formats sprites can be loaded from and saved to.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum ImageFormat {
+    Png,
+    Bmp,
+    /// Binary (`P6`) or plain text (`P3`) PPM, which has no alpha.
+    Ppm,
+}
+
+impl ImageFormat {
+    /// Guesses the format from the extension of `path`.
+    pub fn from_path(path: impl AsRef<Path>) -> Option<Self> {
+        let extension = path.as_ref().extension()?.to_str()?.to_ascii_lowercase();
+        match extension.as_str() {
+            "png" => Some(Self::Png),
+            "bmp" => Some(Self::Bmp),
+            "ppm" => Some(Self::Ppm),
+            _ => None,
+        }
+    }
+
+    /// Guesses the format from the first bytes of a file.
+    pub fn from_bytes(bytes: &[u8]) -> Option<Self> {
+        if bytes.starts_with(b"\x89PNG\r\n\x1a\n") {
+            Some(Self::Png)
+        } else if bytes.starts_with(b"BM") {
+            Some(Self::Bmp)
+        } else if bytes.starts_with(b"P6") || bytes.starts_with(b"P3") {
+            Some(Self::Ppm)
+        } else {
+            None
+        }
+    }
+}
+
+#[derive(Debug)]
+pub enum ImageError {
+    Io(io::Error),
+    /// The file isn't in one of the [`ImageFormat`]s.
+    UnknownFormat,
+    /// The file is in a known format, but uses a feature that isn't supported.
+    Unsupported(String),
+    /// The file is broken or cut short.
+    Malformed(String),
+}
+
+impl fmt::Display for ImageError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Self::Io(e) => write!(f, "{e}"),
+            Self::UnknownFormat => write!(f, "unknown image format"),
+            Self::Unsupported(what) => write!(f, "unsupported image: {what}"),
+            Self::Malformed(what) => write!(f, "malformed image: {what}"),
+        }
+    }
+}
+
+impl error::Error for ImageError {
+    fn source(&self) -> Option<&(dyn error::Error + 'static)> {
+        match self {
+            Self::Io(e) => Some(e),
+            _ => None,
+        }
+    }
+}
+
+impl From<io::Error> for ImageError {
+    fn from(e: io::Error) -> Self {
+        Self::Io(e)
+    }
+}
+
+impl Sprite {
+    /// Loads a PNG, BMP or PPM file, the format is detected from its contents.
+    pub fn load(path: impl AsRef<Path>) -> Result<Self, ImageError> {
+        Self::from_bytes(&fs::read(path)?)
+    }
+
+    pub fn from_bytes(bytes: &[u8]) -> Result<Self, ImageError> {
+        let data = match ImageFormat::from_bytes(bytes).ok_or(ImageError::UnknownFormat)? {
+            ImageFormat::Png => decode_png(bytes)?,
+            ImageFormat::Bmp => decode_bmp(bytes)?,
+            ImageFormat::Ppm => decode_ppm(bytes)?,
+        };
+        Ok(Self { data })
+    }
+
+    /// Saves the sprite in the format matching the extension of `path`.
+    pub fn save(&self, path: impl AsRef<Path>) -> Result<(), ImageError> {
+        let format = ImageFormat::from_path(&path).ok_or(ImageError::UnknownFormat)?;
+        fs::write(path, self.to_bytes(format)?)?;
+        Ok(())
+    }
+
+    /// Encodes the sprite, PPM drops the alpha channel.
+    pub fn to_bytes(&self, format: ImageFormat) -> Result<Vec<u8>, ImageError> {
+        match format {
+            ImageFormat::Png => encode_png(&self.data),
+            ImageFormat::Bmp => encode_bmp(&self.data),
+            ImageFormat::Ppm => Ok(encode_ppm(&self.data)),
+        }
+    }
+}
+
+fn malformed(what: impl Into<String>) -> ImageError {
+    ImageError::Malformed(what.into())
+}
+
+fn decode_png(bytes: &[u8]) -> Result<Vec2d<Color>, ImageError> {
+    let png_error = |e: png::DecodingError| match e {
+        png::DecodingError::IoError(e) => malformed(e.to_string()),
+        png::DecodingError::Format(e) => malformed(e.to_string()),
+        e => ImageError::Unsupported(e.to_string()),
+    };
+    let mut decoder = png::Decoder::new(bytes);
+    decoder.set_transformations(png::Transformations::normalize_to_color8());
+    let mut reader = decoder.read_info().map_err(png_error)?;
+    let mut buffer = vec![0; reader.output_buffer_size()];
+    let info = reader.next_frame(&mut buffer).map_err(png_error)?;
+
+    let (width, height) = (info.width as usize, info.height as usize);
+    let channels = info.color_type.samples();
+    let mut data = Vec2d::new_with(width, height, BLANK);
+    for y in 0..height {
+        let row = &buffer[y * info.line_size..];
+        for x in 0..width {
+            let p = &row[x * channels..(x + 1) * channels];
+            let color = match p {
+                [v] => Color::rgb(*v, *v, *v),
+                [v, a] => Color::rgba(*v, *v, *v, *a),
+                [r, g, b] => Color::rgb(*r, *g, *b),
+                [r, g, b, a] => Color::rgba(*r, *g, *b, *a),
+                _ => unreachable!(),
+            };
+            data.set(x, y, color);
+        }
+    }
+    Ok(data)
+}
+
+fn encode_png(data: &Vec2d<Color>) -> Result<Vec<u8>, ImageError> {
+    let mut bytes = Vec::new();
+    let mut encoder = png::Encoder::new(&mut bytes, data.width() as u32, data.height() as u32);
+    encoder.set_color(png::ColorType::Rgba);
+    encoder.set_depth(png::BitDepth::Eight);
+    let pixels = rows(data)
+        .flatten()
+        .flat_map(|c| [c.r, c.g, c.b, c.a])
+        .collect::<Vec<_>>();
+    encoder
+        .write_header()
+        .and_then(|mut writer| writer.write_image_data(&pixels))
+        .map_err(|e| match e {
+            png::EncodingError::IoError(e) => ImageError::Io(e),
+            e => ImageError::Unsupported(e.to_string()),
+        })?;
+    Ok(bytes)
+}
+
+fn decode_bmp(bytes: &[u8]) -> Result<Vec2d<Color>, ImageError> {
+    let u16_at = |at: usize| {
+        bytes
+            .get(at..at + 2)
+            .map(|b| u16::from_le_bytes([b[0], b[1]]))
+            .ok_or_else(|| malformed("file ends inside the header"))
+    };
+    let u32_at = |at: usize| {
+        bytes
+            .get(at..at + 4)
+            .map(|b| u32::from_le_bytes([b[0], b[1], b[2], b[3]]))
+            .ok_or_else(|| malformed("file ends inside the header"))
+    };
+
+    let pixels_offset = u32_at(10)? as usize;
+    let header_size = u32_at(14)? as usize;
+    if header_size < 40 {
+        return Err(ImageError::Unsupported(format!(
+            "{header_size} byte BMP header"
+        )));
+    }
+    let width = u32_at(18)? as i32;
+    let height = u32_at(22)? as i32;
+    let bits = u16_at(28)?;
+    let compression = u32_at(30)?;
+    if width <= 0 || height == 0 {
+        return Err(malformed(format!("invalid size {width}x{height}")));
+    }
+    // a negative height means rows are stored top to bottom
+    let (width, top_down, height) = (width as usize, height < 0, height.unsigned_abs() as usize);
+
+    let masks = match (compression, bits) {
+        (0, 32) => None,
+        (3, 32) => {
+            let alpha = if header_size >= 56 { u32_at(66)? } else { 0 };
+            Some([u32_at(54)?, u32_at(58)?, u32_at(62)?, alpha])
+        }
+        (0, 1 | 4 | 8 | 24) => None,
+        _ => {
+            return Err(ImageError::Unsupported(format!(
+                "{bits} bit BMP with compression {compression}"
+            )))
+        }
+    };
+    let palette = if bits <= 8 {
+        let count = match u32_at(46)? {
+            0 => 1 << bits,
+            count => count as usize,
+        };
+        (0..count)
+            .map(|i| {
+                let at = 14 + header_size + i * 4;
+                let b = bytes
+                    .get(at..at + 3)
+                    .ok_or_else(|| malformed("file ends inside the palette"))?;
+                Ok(Color::rgb(b[2], b[1], b[0]))
+            })
+            .collect::<Result<Vec<_>, ImageError>>()?
+    } else {
+        Vec::new()
+    };
+
+    let row_size = (width * bits as usize).div_ceil(32) * 4;
+    let pixels = bytes
+        .get(pixels_offset..)
+        .filter(|pixels| {
+            row_size
+                .checked_mul(height)
+                .is_some_and(|size| pixels.len() >= size)
+        })
+        .ok_or_else(|| malformed("file ends before the last row"))?;
+
+    let mut data = Vec2d::new_with(width, height, BLANK);
+    let mut any_alpha = false;
+    for row_index in 0..height {
+        let row = &pixels[row_index * row_size..(row_index + 1) * row_size];
+        let y = if top_down {
+            row_index
+        } else {
+            height - 1 - row_index
+        };
+        for x in 0..width {
+            let color = match bits {
+                32 => {
+                    let p = u32::from_le_bytes([
+                        row[x * 4],
+                        row[x * 4 + 1],
+                        row[x * 4 + 2],
+                        row[x * 4 + 3],
+                    ]);
+                    let [r, g, b, a] = masks
+                        .unwrap_or([0xff0000, 0xff00, 0xff, 0xff000000])
+                        .map(|mask| masked(p, mask));
+                    any_alpha |= a != 0;
+                    Color::rgba(r, g, b, a)
+                }
+                24 => Color::rgb(row[x * 3 + 2], row[x * 3 + 1], row[x * 3]),
+                bits => {
+                    let bit = x * bits as usize;
+                    let byte = row[bit / 8];
+                    let shift = 8 - bits as usize - bit % 8;
+                    let index = (byte >> shift) as usize & ((1 << bits) - 1);
+                    *palette
+                        .get(index)
+                        .ok_or_else(|| malformed(format!("color {index} is not in the palette")))?
+                }
+            };
+            data.set(x, y, color);
+        }
+    }
+    // plenty of programs leave the alpha byte at 0 in 32 bit images that aren't transparent
+    if bits == 32 && !any_alpha {
+        for y in 0..height {
+            for x in 0..width {
+                data.get_mut(x, y).a = 255;
+            }
+        }
+    }
+    Ok(data)
+}
+
+/// The 8 bit value of the channel selected by `mask`.
+fn masked(pixel: u32, mask: u32) -> u8 {
+    if mask == 0 {
+        return 0;
+    }
+    // in 64 bits, a 32 bit wide mask would overflow
+    let value = ((pixel & mask) >> mask.trailing_zeros()) as u64;
+    let max = (mask >> mask.trailing_zeros()) as u64;
+    ((value * 255 + max / 2) / max) as u8
+}
+
+fn encode_bmp(data: &Vec2d<Color>) -> Result<Vec<u8>, ImageError> {
+    const HEADER_SIZE: u32 = 108;
+    let pixels_offset = 14 + HEADER_SIZE;
+    let too_large = || {
+        ImageError::Unsupported(format!(
+            "{}x{} is too large for a BMP",
+            data.width(),
+            data.height()
+        ))
+    };
+    let width = i32::try_from(data.width()).map_err(|_| too_large())? as u32;
+    let height = i32::try_from(data.height()).map_err(|_| too_large())? as u32;
+    let image_size = (width as u64 * height as u64 * 4)
+        .try_into()
+        .ok()
+        .filter(|size: &u32| size.checked_add(pixels_offset).is_some())
+        .ok_or_else(too_large)?;
+
+    let mut bytes = Vec::with_capacity((pixels_offset + image_size) as usize);
+    bytes.extend_from_slice(b"BM");
+    bytes.extend_from_slice(&(pixels_offset + image_size).to_le_bytes());
+    bytes.extend_from_slice(&[0; 4]);
+    bytes.extend_from_slice(&pixels_offset.to_le_bytes());
+
+    // BITMAPV4HEADER, so the alpha channel is kept
+    bytes.extend_from_slice(&HEADER_SIZE.to_le_bytes());
+    bytes.extend_from_slice(&width.to_le_bytes());
+    bytes.extend_from_slice(&(-(height as i32)).to_le_bytes());
+    bytes.extend_from_slice(&1u16.to_le_bytes());
+    bytes.extend_from_slice(&32u16.to_le_bytes());
+    bytes.extend_from_slice(&3u32.to_le_bytes());
+    bytes.extend_from_slice(&image_size.to_le_bytes());
+    bytes.extend_from_slice(&[0; 16]);
+    for mask in [0xff0000u32, 0xff00, 0xff, 0xff000000] {
+        bytes.extend_from_slice(&mask.to_le_bytes());
+    }
+    bytes.extend_from_slice(b"BGRs");
+    bytes.extend_from_slice(&[0; 48]);
+
+    for color in rows(data).flatten() {
+        bytes.extend_from_slice(&[color.b, color.g, color.r, color.a]);
+    }
+    Ok(bytes)
+}
+
+fn decode_ppm(bytes: &[u8]) -> Result<Vec2d<Color>, ImageError> {
+    let mut at = 2;
+    let mut next_number = || -> Result<usize, ImageError> {
+        loop {
+            match bytes.get(at) {
+                Some(b'#') => {
+                    while bytes.get(at).is_some_and(|b| *b != b'\n') {
+                        at += 1;
+                    }
+                }
+                Some(b) if b.is_ascii_whitespace() => at += 1,
+                _ => break,
+            }
+        }
+        let start = at;
+        while bytes.get(at).is_some_and(u8::is_ascii_digit) {
+            at += 1;
+        }
+        std::str::from_utf8(&bytes[start..at])
+            .unwrap()
+            .parse()
+            .map_err(|_| malformed(format!("expected a number at byte {start}")))
+    };
+
+    let (width, height, max) = (next_number()?, next_number()?, next_number()?);
+    if max == 0 || max > 65535 {
+        return Err(malformed(format!("invalid maximum value {max}")));
+    }
+    let count = width
+        .checked_mul(height)
+        .and_then(|n| n.checked_mul(3))
+        .ok_or_else(|| malformed(format!("invalid size {width}x{height}")))?;
+    let scale = |v: usize| -> Result<u8, ImageError> {
+        if v > max {
+            return Err(malformed(format!("value {v} is above the maximum {max}")));
+        }
+        Ok(((v * 255 + max / 2) / max) as u8)
+    };
+
+    let values = if bytes.starts_with(b"P6") {
+        // a single whitespace separates the header from the binary data
+        let start = at + 1;
+        let sample_size = if max < 256 { 1 } else { 2 };
+        let samples = bytes
+            .get(start..)
+            .filter(|samples| {
+                count
+                    .checked_mul(sample_size)
+                    .is_some_and(|size| samples.len() >= size)
+            })
+            .ok_or_else(|| malformed("file ends before the last pixel"))?;
+        samples
+            .chunks(sample_size)
+            .take(count)
+            .map(|s| scale(s.iter().fold(0, |v, b| v << 8 | *b as usize)))
+            .collect::<Result<Vec<_>, _>>()?
+    } else {
+        (0..count)
+            .map(|_| scale(next_number()?))
+            .collect::<Result<Vec<_>, _>>()?
+    };
+
+    let mut data = Vec2d::new_with(width, height, BLANK);
+    for (i, rgb) in values.chunks(3).enumerate() {
+        data.set(i % width, i / width, Color::rgb(rgb[0], rgb[1], rgb[2]));
+    }
+    Ok(data)
+}
+
+fn encode_ppm(data: &Vec2d<Color>) -> Vec<u8> {
+    let mut bytes = format!("P6\n{} {}\n255\n", data.width(), data.height()).into_bytes();
+    for color in rows(data).flatten() {
+        bytes.extend_from_slice(&[color.r, color.g, color.b]);
+    }
+    bytes
+}
+
+fn rows(data: &Vec2d<Color>) -> impl Iterator<Item = impl Iterator<Item = Color> + '_> + '_ {
+    (0..data.height()).map(move |y| (0..data.width()).map(move |x| *data.get(x, y)))
+}
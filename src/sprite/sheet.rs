@@ -0,0 +1,148 @@
+use super::{ImageError, Sprite};
+use crate::{ctx, Context, Rectangle, Vec2};
+use std::collections::HashMap;
+
+/// One sprite holding many frames or tiles, each a region that can be drawn on its own.
+pub struct SpriteSheet {
+    sprite: Sprite,
+    regions: Vec<Rectangle>,
+    names: HashMap<String, usize>,
+}
+
+impl SpriteSheet {
+    /// A sheet with no regions yet, see [`SpriteSheet::insert`].
+    pub fn new(sprite: Sprite) -> Self {
+        Self {
+            sprite,
+            regions: Vec::new(),
+            names: HashMap::new(),
+        }
+    }
+
+    /// Slices `sprite` into `cell_width` x `cell_height` cells, numbered left to right
+    /// then top to bottom. Cells cut short by the edge of the sprite are left out, and
+    /// there are no cells at all unless both sizes are at least a pixel.
+    pub fn grid(sprite: Sprite, cell_width: f32, cell_height: f32) -> Self {
+        let valid = |size: f32| size >= 1. && size.is_finite();
+        if !valid(cell_width) || !valid(cell_height) {
+            return Self::new(sprite);
+        }
+        let columns = (sprite.width() / cell_width).floor().max(0.) as usize;
+        let rows = (sprite.height() / cell_height).floor().max(0.) as usize;
+        let mut sheet = Self::new(sprite);
+        for row in 0..rows {
+            for column in 0..columns {
+                let (x, y) = (column as f32 * cell_width, row as f32 * cell_height);
+                sheet
+                    .regions
+                    .push(Rectangle::new(x, y, cell_width, cell_height));
+            }
+        }
+        sheet
+    }
+
+    /// Slices `sprite` into the regions listed in `manifest`, one `name x y width height`
+    /// per line, numbered in order. Blank lines and lines starting with `//` are skipped.
+    ///
+    /// ```text
+    /// // player
+    /// idle 0 0 8 8
+    /// jump 8 0 8 8
+    /// ```
+    pub fn with_manifest(sprite: Sprite, manifest: &str) -> Result<Self, ImageError> {
+        let mut sheet = Self::new(sprite);
+        for (n, line) in manifest.lines().enumerate() {
+            let line = line.trim();
+            if line.is_empty() || line.starts_with("//") {
+                continue;
+            }
+            let parts = line.split_whitespace().collect::<Vec<_>>();
+            let numbers = parts[1..]
+                .iter()
+                .map(|part| part.parse::<f32>())
+                .collect::<Result<Vec<_>, _>>();
+            let (name, [x, y, w, h]) = match (parts[0], numbers.as_deref()) {
+                (name, Ok(&[x, y, w, h])) => (name, [x, y, w, h]),
+                _ => {
+                    return Err(ImageError::Malformed(format!(
+                        "line {}: expected `name x y width height`, got {line:?}",
+                        n + 1
+                    )))
+                }
+            };
+            if sheet.names.contains_key(name) {
+                return Err(ImageError::Malformed(format!(
+                    "line {}: {name:?} is already defined",
+                    n + 1
+                )));
+            }
+            sheet.insert(name, Rectangle::new(x, y, w, h));
+        }
+        Ok(sheet)
+    }
+
+    /// Adds a named region and returns its index. If the name is already taken its
+    /// region is replaced and keeps its index.
+    pub fn insert(&mut self, name: impl Into<String>, region: Rectangle) -> usize {
+        let name = name.into();
+        if let Some(&index) = self.names.get(&name) {
+            self.regions[index] = region;
+            return index;
+        }
+        let index = self.push(region);
+        self.names.insert(name, index);
+        index
+    }
+
+    /// Adds a region without a name and returns its index.
+    pub fn push(&mut self, region: Rectangle) -> usize {
+        self.regions.push(region);
+        self.regions.len() - 1
+    }
+
+    pub fn sprite(&self) -> &Sprite {
+        &self.sprite
+    }
+
+    pub fn len(&self) -> usize {
+        self.regions.len()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.regions.is_empty()
+    }
+
+    pub fn region(&self, index: usize) -> Option<Rectangle> {
+        self.regions.get(index).copied()
+    }
+
+    pub fn index_of(&self, name: &str) -> Option<usize> {
+        self.names.get(name).copied()
+    }
+
+    pub fn named(&self, name: &str) -> Option<Rectangle> {
+        self.region(self.index_of(name)?)
+    }
+
+    pub fn draw(&self, index: usize, x: f32, y: f32) {
+        self.draw_with_ctx(ctx(), index, x, y);
+    }
+
+    /// Draws region `index` with its top left corner at `(x, y)`, nothing if there's no such region.
+    pub fn draw_with_ctx(&self, ctx: &mut Context, index: usize, x: f32, y: f32) {
+        if let Some(region) = self.region(index) {
+            self.sprite
+                .draw_region_with_ctx(ctx, region, Vec2::new(x, y));
+        }
+    }
+
+    pub fn draw_named(&self, name: &str, x: f32, y: f32) {
+        self.draw_named_with_ctx(ctx(), name, x, y);
+    }
+
+    pub fn draw_named_with_ctx(&self, ctx: &mut Context, name: &str, x: f32, y: f32) {
+        if let Some(index) = self.index_of(name) {
+            self.draw_with_ctx(ctx, index, x, y);
+        }
+    }
+}
@@ -0,0 +1,189 @@
+use super::{ImageError, Sprite};
+use crate::{Color, Vec2d, BLANK};
+use std::{collections::HashMap, fmt::Write};
+
+const KEY_CHARS: &str = "abcdefghijklmnopqrstuvwxyzABCDEFGHIJKLMNOPQRSTUVWXYZ0123456789";
+
+impl Sprite {
+    /// Parses a sprite from text: a legend mapping characters to colors,
+    /// then the pixel grid, one character per pixel.
+    ///
+    /// ```text
+    /// . 00000000
+    /// r e62937
+    /// w ffffff
+    /// ---
+    /// .rr.
+    /// rwwr
+    /// .rr.
+    /// ```
+    ///
+    /// Colors are `rrggbb` or `rrggbbaa` hex, optionally starting with `#`.
+    /// Blank lines and lines starting with `//` in the legend are skipped.
+    pub fn from_text(text: &str) -> Result<Self, ImageError> {
+        let malformed =
+            |line: usize, what: String| ImageError::Malformed(format!("line {}: {what}", line + 1));
+        let mut lines = text.lines().enumerate();
+        let mut legend = Vec::new();
+        for (n, line) in lines.by_ref() {
+            let line = line.trim();
+            if line == "---" {
+                break;
+            }
+            if line.is_empty() || line.starts_with("//") {
+                continue;
+            }
+            let mut chars = line.chars();
+            let key = chars.next().unwrap();
+            let hex = chars.as_str().trim();
+            let color = Color::parse_hex(hex)
+                .ok_or_else(|| malformed(n, format!("invalid color {hex:?} for {key:?}")))?;
+            legend.push((key, color));
+        }
+
+        let mut rows = lines.collect::<Vec<_>>();
+        while rows.last().is_some_and(|(_, row)| row.trim().is_empty()) {
+            rows.pop();
+        }
+        let grid = rows.iter().map(|(_, row)| *row).collect::<Vec<_>>();
+        Self::from_rows(&legend, &grid).map_err(|e| match e {
+            ImageError::Malformed(what) => {
+                let first_row = rows.first().map_or(0, |(n, _)| *n);
+                ImageError::Malformed(format!("grid starting at line {}: {what}", first_row + 1))
+            }
+            e => e,
+        })
+    }
+
+    /// Builds a sprite from rows of characters, each one looked up in `legend`.
+    pub fn from_rows(legend: &[(char, Color)], rows: &[&str]) -> Result<Self, ImageError> {
+        let legend = legend.iter().copied().collect::<HashMap<_, _>>();
+        let width = rows.first().map_or(0, |row| row.chars().count());
+        let mut data = Vec2d::new_with(width, rows.len(), BLANK);
+        for (y, row) in rows.iter().enumerate() {
+            if row.chars().count() != width {
+                return Err(ImageError::Malformed(format!(
+                    "row {y} is not {width} pixels wide"
+                )));
+            }
+            for (x, key) in row.chars().enumerate() {
+                let color = legend.get(&key).ok_or_else(|| {
+                    ImageError::Malformed(format!("{key:?} in row {y} is not in the legend"))
+                })?;
+                data.set(x, y, *color);
+            }
+        }
+        Ok(Self { data })
+    }
+
+    /// Writes the sprite in the format read by [`Sprite::from_text`].
+    /// Fully transparent pixels are written as `.`.
+    pub fn to_text(&self) -> String {
+        let mut legend = Vec::<Color>::new();
+        for y in 0..self.data.height() {
+            for x in 0..self.data.width() {
+                let color = *self.data.get(x, y);
+                let color = if color.a == 0 { BLANK } else { color };
+                if !legend.contains(&color) {
+                    legend.push(color);
+                }
+            }
+        }
+        legend.sort_by_key(|color| *color != BLANK);
+
+        let mut keys = KEY_CHARS.chars().chain(
+            // past ASCII, any printable character will do
+            ('\u{a1}'..).filter(|c| !c.is_whitespace()),
+        );
+        let legend = legend
+            .into_iter()
+            .map(|color| match color {
+                BLANK => ('.', color),
+                _ => (keys.next().unwrap(), color),
+            })
+            .collect::<Vec<_>>();
+
+        let mut out = String::new();
+        for (key, c) in &legend {
+            writeln!(out, "{key} {:02x}{:02x}{:02x}{:02x}", c.r, c.g, c.b, c.a).unwrap();
+        }
+        out.push_str("---\n");
+        for y in 0..self.data.height() {
+            for x in 0..self.data.width() {
+                let color = *self.data.get(x, y);
+                let key = legend
+                    .iter()
+                    .find(|(_, c)| *c == color || (color.a == 0 && *c == BLANK))
+                    .unwrap()
+                    .0;
+                out.push(key);
+            }
+            out.push('\n');
+        }
+        out
+    }
+}
+
+/// Builds a [`Sprite`] from a legend and rows of ASCII characters, checking at compile time
+/// that every row is as wide as the first and only uses characters from the legend.
+///
+/// ```
+/// use teremder::*;
+///
+/// let heart = sprite! {
+///     '.' => BLANK,
+///     'r' => RED;
+///     ".r.r.",
+///     "rrrrr",
+///     ".rrr.",
+///     "..r..",
+/// };
+/// assert_eq!(heart.width(), 5.);
+/// ```
+///
+/// Larger art can be kept in its own file with [`Sprite::from_text`] and `include_str!`.
+#[macro_export]
+macro_rules! sprite {
+    ($($key:literal => $color:expr),* $(,)?; $($row:literal),* $(,)?) => {{
+        const _: () = $crate::check_sprite_rows(&[$($key),*], &[$($row),*]);
+        $crate::Sprite::from_rows(&[$(($key, $color)),*], &[$($row),*]).unwrap()
+    }};
+}
+
+#[doc(hidden)]
+pub const fn check_sprite_rows(keys: &[char], rows: &[&str]) {
+    let mut i = 0;
+    while i < keys.len() {
+        if !keys[i].is_ascii() {
+            panic!("sprite! only supports ASCII characters");
+        }
+        i += 1;
+    }
+    if rows.is_empty() {
+        return;
+    }
+    let width = rows[0].len();
+    let mut y = 0;
+    while y < rows.len() {
+        let row = rows[y].as_bytes();
+        if row.len() != width {
+            panic!("every row of a sprite! must be as wide as the first one");
+        }
+        let mut x = 0;
+        while x < row.len() {
+            let mut found = false;
+            let mut k = 0;
+            while k < keys.len() {
+                if keys[k] as u32 == row[x] as u32 {
+                    found = true;
+                }
+                k += 1;
+            }
+            if !found {
+                panic!("a row of a sprite! uses a character that is not in the legend");
+            }
+            x += 1;
+        }
+        y += 1;
+    }
+}
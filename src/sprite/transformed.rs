@@ -0,0 +1,145 @@
+use super::Sprite;
+use crate::{ctx, drawing::rect_points, Color, Context, Rectangle, Transform, Vec2, BLANK};
+
+/// How a sprite's pixels are read when it's scaled or rotated.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub enum Filter {
+    /// Sharp pixels, best for pixel art.
+    #[default]
+    Nearest,
+    /// Blends the 4 closest pixels, smoother but blurrier.
+    Bilinear,
+}
+
+/// Options for [`Sprite::draw_ex`].
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct DrawSpriteParams {
+    /// Part of the sprite to draw (in sprite pixels), `None` for all of it.
+    pub source: Option<Rectangle>,
+    pub scale: Vec2,
+    /// In radians, clockwise, around `pivot`.
+    pub rotation: f32,
+    /// In sprite pixels from the top left corner of `source`, `None` for its center.
+    /// It stays where it would be without rotation.
+    pub pivot: Option<Vec2>,
+    pub flip_x: bool,
+    pub flip_y: bool,
+    pub filter: Filter,
+}
+
+impl Default for DrawSpriteParams {
+    fn default() -> Self {
+        Self {
+            source: None,
+            scale: Vec2::ONE,
+            rotation: 0.,
+            pivot: None,
+            flip_x: false,
+            flip_y: false,
+            filter: Filter::default(),
+        }
+    }
+}
+
+impl Sprite {
+    pub fn draw_ex(&self, x: f32, y: f32, params: &DrawSpriteParams) {
+        self.draw_ex_with_ctx(ctx(), x, y, params);
+    }
+
+    /// Draws the sprite scaled, rotated and flipped, with the top left corner of the
+    /// unrotated sprite at `(x, y)`. Every screen pixel it covers is mapped back
+    /// into the sprite, so there are no holes.
+    pub fn draw_ex_with_ctx(&self, ctx: &mut Context, x: f32, y: f32, params: &DrawSpriteParams) {
+        let src = params.source.unwrap_or(self.bounds());
+        let src = Rectangle::new(src.x.floor(), src.y.floor(), src.w.trunc(), src.h.trunc());
+        let scale = params.scale;
+        let Some(visible) = src.intersection(&self.bounds()) else {
+            return;
+        };
+        if scale.x == 0. || scale.y == 0. {
+            return;
+        }
+
+        let size = Vec2::new(src.w, src.h);
+        let pivot = params.pivot.unwrap_or(size / 2.);
+        let anchor = Vec2::new(x, y) + pivot * scale;
+        let flip = |p: Vec2| {
+            Vec2::new(
+                if params.flip_x { size.x - p.x } else { p.x },
+                if params.flip_y { size.y - p.y } else { p.y },
+            )
+        };
+        let camera = ctx.transform().unwrap_or(Transform::IDENTITY);
+        let to_screen =
+            |p: Vec2| camera.apply(anchor + ((flip(p) - pivot) * scale).rotate(params.rotation));
+        let inverse_camera = camera.inverse();
+        let to_sprite = |p: Vec2| {
+            let p = (inverse_camera.apply(p) - anchor).rotate(-params.rotation);
+            flip(p / scale + pivot)
+        };
+
+        ctx.untransformed(|ctx| {
+            let corners = rect_points(0., 0., size.x, size.y).map(to_screen);
+            let (min, max) = corners
+                .iter()
+                .fold((Vec2::MAX, Vec2::MIN), |(min, max), p| {
+                    (min.min(p), max.max(p))
+                });
+            let x_start = min.x.floor().max(0.) as usize;
+            let y_start = min.y.floor().max(0.) as usize;
+            let x_end = max.x.ceil().clamp(0., ctx.screen_width()) as usize;
+            let y_end = max.y.ceil().clamp(0., ctx.screen_height()) as usize;
+
+            for my in y_start..y_end {
+                for mx in x_start..x_end {
+                    let local = to_sprite(Vec2::new(mx as f32 + 0.5, my as f32 + 0.5));
+                    let p = local + Vec2::new(src.x, src.y);
+                    if !visible.contains(&p) {
+                        continue;
+                    }
+                    let color = match params.filter {
+                        Filter::Nearest => self.get_pixel(p.x, p.y),
+                        Filter::Bilinear => self.sample_bilinear(visible, p),
+                    };
+                    ctx.set_pixel(mx as f32, my as f32, color);
+                }
+            }
+        });
+    }
+
+    /// Blends the 4 pixels around `p` (pixel centers are at .5), staying inside `region`.
+    fn sample_bilinear(&self, region: Rectangle, p: Vec2) -> Color {
+        let (x, y) = (p.x - 0.5, p.y - 0.5);
+        let (x0, y0) = (x.floor(), y.floor());
+        let (fx, fy) = (x - x0, y - y0);
+        let pixel = |x: f32, y: f32| {
+            let x = x.clamp(region.x, region.x + region.w - 1.);
+            let y = y.clamp(region.y, region.y + region.h - 1.);
+            self.get_pixel(x, y)
+        };
+        let samples = [
+            (pixel(x0, y0), (1. - fx) * (1. - fy)),
+            (pixel(x0 + 1., y0), fx * (1. - fy)),
+            (pixel(x0, y0 + 1.), (1. - fx) * fy),
+            (pixel(x0 + 1., y0 + 1.), fx * fy),
+        ];
+        // weighted by alpha, so transparent pixels don't darken the edges
+        let (mut r, mut g, mut b, mut a) = (0., 0., 0., 0.);
+        for (color, weight) in samples {
+            let w = weight * color.a as f32;
+            r += color.r as f32 * w;
+            g += color.g as f32 * w;
+            b += color.b as f32 * w;
+            a += w;
+        }
+        if a <= 0. {
+            return BLANK;
+        }
+        Color::rgba(
+            (r / a).round() as u8,
+            (g / a).round() as u8,
+            (b / a).round() as u8,
+            a.round() as u8,
+        )
+    }
+}
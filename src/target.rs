@@ -0,0 +1,105 @@
+use crate::{BlendMode, Camera2D, Color, Context, Pixel, Rectangle, Vec2, Vec2d};
+
+/// Something the drawing functions can write pixels into: the screen or a sprite.
+pub(crate) trait Canvas {
+    fn width(&self) -> usize;
+    fn height(&self) -> usize;
+    fn get(&self, x: usize, y: usize) -> Color;
+    fn blend(&mut self, x: usize, y: usize, color: Color, mode: BlendMode);
+    fn replace(&mut self, x: usize, y: usize, color: Color);
+    fn fill(&mut self, color: Color);
+}
+
+impl Canvas for Vec2d<Pixel> {
+    fn width(&self) -> usize {
+        Vec2d::width(self)
+    }
+
+    fn height(&self) -> usize {
+        Vec2d::height(self)
+    }
+
+    fn get(&self, x: usize, y: usize) -> Color {
+        let pixel = Vec2d::get(self, x, y);
+        Color::rgb(pixel.r, pixel.g, pixel.b)
+    }
+
+    fn blend(&mut self, x: usize, y: usize, color: Color, mode: BlendMode) {
+        let pixel = self.get_mut(x, y);
+        *pixel = mode.blend(*pixel, color);
+    }
+
+    fn replace(&mut self, x: usize, y: usize, color: Color) {
+        self.set(x, y, opaque(color));
+    }
+
+    fn fill(&mut self, color: Color) {
+        Vec2d::fill(self, opaque(color));
+    }
+}
+
+/// Sprites keep their alpha, so what's drawn into them can be blended again later.
+impl Canvas for Vec2d<Color> {
+    fn width(&self) -> usize {
+        Vec2d::width(self)
+    }
+
+    fn height(&self) -> usize {
+        Vec2d::height(self)
+    }
+
+    fn get(&self, x: usize, y: usize) -> Color {
+        *Vec2d::get(self, x, y)
+    }
+
+    fn blend(&mut self, x: usize, y: usize, color: Color, mode: BlendMode) {
+        let dst = self.get_mut(x, y);
+        *dst = mode.blend_color(*dst, color);
+    }
+
+    fn replace(&mut self, x: usize, y: usize, color: Color) {
+        self.set(x, y, color);
+    }
+
+    fn fill(&mut self, color: Color) {
+        Vec2d::fill(self, color);
+    }
+}
+
+fn opaque(color: Color) -> Pixel {
+    Pixel {
+        r: color.r,
+        g: color.g,
+        b: color.b,
+    }
+}
+
+/// A sprite being drawn to, with the camera, clipping and window it put aside.
+pub(crate) struct Target {
+    pub(crate) data: Vec2d<Color>,
+    pub(crate) camera: Option<Camera2D>,
+    pub(crate) clip_stack: Vec<Rectangle>,
+    pub(crate) origin: Vec2,
+    pub(crate) view_size: Option<Vec2>,
+}
+
+impl Context {
+    /// Where drawing currently goes, the innermost sprite being drawn to or else the current layer.
+    pub(crate) fn canvas(&self) -> &dyn Canvas {
+        match self.targets.last() {
+            Some(target) => &target.data,
+            None if self.current_layer != 0 => &self.layers[&self.current_layer].buffer,
+            None => &self.drawing_buffer,
+        }
+    }
+
+    pub(crate) fn canvas_mut(&mut self) -> &mut dyn Canvas {
+        match self.targets.last_mut() {
+            Some(target) => &mut target.data,
+            None if self.current_layer != 0 => {
+                &mut self.layers.get_mut(&self.current_layer).unwrap().buffer
+            }
+            None => &mut self.drawing_buffer,
+        }
+    }
+}
@@ -0,0 +1,197 @@
+//! Snapshot testing for anything drawn on a [`Context`].
+//!
+//! Snapshots are stored as text, a palette mapping characters to colors
+//! followed by the pixel grid, so they can be reviewed in a diff (keys get
+//! longer than one character when there are a lot of colors):
+//!
+//! ```text
+//! 4x2
+//! a 000000
+//! b e62937
+//! ---
+//! abba
+//! aaaa
+//! ```
+//!
+//! A missing snapshot is written instead of compared, and all snapshots are
+//! rewritten when the `TEREMDER_UPDATE_SNAPSHOTS` environment variable is set.
+
+use crate::{Color, Context, Pixel, Vec2d};
+use std::{collections::HashMap, fmt::Write, fs, path::Path};
+
+const PALETTE_CHARS: &str =
+    "abcdefghijklmnopqrstuvwxyzABCDEFGHIJKLMNOPQRSTUVWXYZ0123456789!$%&*+/<=>?@^_~";
+const MAX_LISTED_MISMATCHES: usize = 10;
+
+pub fn encode(buffer: &Vec2d<Pixel>) -> String {
+    let mut palette = Vec::<Pixel>::new();
+    let mut indices = Vec::with_capacity(buffer.width() * buffer.height());
+    for y in 0..buffer.height() {
+        for x in 0..buffer.width() {
+            let pixel = *buffer.get(x, y);
+            let index = match palette.iter().position(|p| *p == pixel) {
+                Some(index) => index,
+                None => {
+                    palette.push(pixel);
+                    palette.len() - 1
+                }
+            };
+            indices.push(index);
+        }
+    }
+
+    // keys get longer once there are more colors than palette chars
+    let chars = PALETTE_CHARS.chars().collect::<Vec<_>>();
+    let mut key_len = 1;
+    while chars.len().pow(key_len) < palette.len() {
+        key_len += 1;
+    }
+    let key = |mut index: usize| {
+        let mut key = vec![' '; key_len as usize];
+        for c in key.iter_mut().rev() {
+            *c = chars[index % chars.len()];
+            index /= chars.len();
+        }
+        key.into_iter().collect::<String>()
+    };
+
+    let mut out = format!("{}x{}\n", buffer.width(), buffer.height());
+    for (i, p) in palette.iter().enumerate() {
+        writeln!(out, "{} {}", key(i), hex(p)).unwrap();
+    }
+    out.push_str("---\n");
+    for row in indices.chunks(buffer.width().max(1)) {
+        for index in row {
+            out.push_str(&key(*index));
+        }
+        out.push('\n');
+    }
+    out
+}
+
+pub fn decode(text: &str) -> Result<Vec2d<Pixel>, String> {
+    let mut lines = text.lines();
+    let size = lines.next().ok_or("missing size line")?;
+    let (width, height) = size
+        .split_once('x')
+        .and_then(|(w, h)| Some((w.parse::<usize>().ok()?, h.parse::<usize>().ok()?)))
+        .ok_or_else(|| format!("invalid size line: {size:?}"))?;
+
+    let mut palette = HashMap::new();
+    let mut key_len = 1;
+    for line in lines.by_ref() {
+        if line == "---" {
+            break;
+        }
+        let Some((key, pixel)) = line
+            .split_once(' ')
+            .and_then(|(key, hex)| Some((key, parse_hex(hex)?)))
+        else {
+            return Err(format!("invalid palette line: {line:?}"));
+        };
+        key_len = key.chars().count();
+        palette.insert(key.to_string(), pixel);
+    }
+
+    let mut buffer = Vec2d::new(width, height);
+    let mut rows = 0;
+    for (y, line) in lines.enumerate() {
+        let chars = line.chars().collect::<Vec<_>>();
+        if y >= height || chars.len() != width * key_len {
+            return Err(format!("pixel grid doesn't match size {width}x{height}"));
+        }
+        for (x, key) in chars.chunks(key_len).enumerate() {
+            let key = key.iter().collect::<String>();
+            let pixel = palette
+                .get(&key)
+                .ok_or_else(|| format!("{key:?} is not in the palette"))?;
+            buffer.set(x, y, *pixel);
+        }
+        rows += 1;
+    }
+    if rows != height {
+        return Err(format!("pixel grid doesn't match size {width}x{height}"));
+    }
+    Ok(buffer)
+}
+
+/// Compares the drawing buffer of `ctx` against the snapshot at `path`.
+pub fn assert_snapshot(ctx: &Context, path: impl AsRef<Path>) {
+    assert_buffer_snapshot(ctx.drawing_buffer(), path);
+}
+
+pub fn assert_buffer_snapshot(buffer: &Vec2d<Pixel>, path: impl AsRef<Path>) {
+    let path = path.as_ref();
+    if std::env::var_os("TEREMDER_UPDATE_SNAPSHOTS").is_some() || !path.exists() {
+        if let Some(parent) = path.parent() {
+            fs::create_dir_all(parent).unwrap();
+        }
+        fs::write(path, encode(buffer)).unwrap();
+        return;
+    }
+
+    let text = fs::read_to_string(path).unwrap();
+    let expected = decode(&text).unwrap_or_else(|e| panic!("{}: {e}", path.display()));
+    if let Some(diff) = diff(&expected, buffer) {
+        panic!("snapshot {} doesn't match:\n{diff}", path.display());
+    }
+}
+
+/// Describes where `actual` differs from `expected`, `None` if they're the same.
+pub fn diff(expected: &Vec2d<Pixel>, actual: &Vec2d<Pixel>) -> Option<String> {
+    if expected.width() != actual.width() || expected.height() != actual.height() {
+        return Some(format!(
+            "expected size {}x{}, got {}x{}",
+            expected.width(),
+            expected.height(),
+            actual.width(),
+            actual.height()
+        ));
+    }
+
+    let mut mismatches = Vec::new();
+    let mut map = String::new();
+    for y in 0..actual.height() {
+        for x in 0..actual.width() {
+            let (e, a) = (expected.get(x, y), actual.get(x, y));
+            if e == a {
+                map.push('.');
+            } else {
+                map.push('X');
+                mismatches.push((x, y, *e, *a));
+            }
+        }
+        map.push('\n');
+    }
+    if mismatches.is_empty() {
+        return None;
+    }
+
+    let mut out = format!("{} pixels differ (X):\n{map}", mismatches.len());
+    for (x, y, e, a) in mismatches.iter().take(MAX_LISTED_MISMATCHES) {
+        writeln!(out, "  ({x}, {y}): expected {}, got {}", hex(e), hex(a)).unwrap();
+    }
+    if mismatches.len() > MAX_LISTED_MISMATCHES {
+        writeln!(
+            out,
+            "  ... and {} more",
+            mismatches.len() - MAX_LISTED_MISMATCHES
+        )
+        .unwrap();
+    }
+    Some(out)
+}
+
+fn hex(p: &Pixel) -> String {
+    format!("{:02x}{:02x}{:02x}", p.r, p.g, p.b)
+}
+
+/// Snapshots only hold opaque `rrggbb` colors.
+fn parse_hex(s: &str) -> Option<Pixel> {
+    let color = Color::parse_hex(s).filter(|_| s.len() == 6)?;
+    Some(Pixel {
+        r: color.r,
+        g: color.g,
+        b: color.b,
+    })
+}
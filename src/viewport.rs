@@ -0,0 +1,94 @@
+use crate::{Camera2D, Context, Rectangle, Vec2, Window};
+
+/// A part of the screen showing the world through its own camera, e.g. one player's half
+/// of a split screen.
+#[derive(Debug, Clone, Copy, PartialEq, Default)]
+pub struct Viewport {
+    pub rect: Rectangle,
+    /// Its offset is relative to the viewport, not the screen.
+    pub camera: Camera2D,
+}
+
+impl Viewport {
+    /// A viewport with `target` in its center.
+    pub fn new(rect: Rectangle, target: Vec2) -> Self {
+        Self {
+            rect,
+            camera: Camera2D::new(target, Vec2::new(rect.w, rect.h) / 2.),
+        }
+    }
+
+    /// Splits `area` into `count` viewports next to each other, left to right.
+    pub fn side_by_side(area: Rectangle, count: usize) -> Vec<Self> {
+        let width = (area.w / count as f32).floor();
+        (0..count)
+            .map(|i| {
+                let x = area.x + width * i as f32;
+                let w = if i + 1 == count {
+                    area.x + area.w - x
+                } else {
+                    width
+                };
+                Self::new(Rectangle::new(x, area.y, w, area.h), Vec2::ZERO)
+            })
+            .collect()
+    }
+
+    /// Splits `area` into `count` viewports on top of each other, top to bottom.
+    pub fn stacked(area: Rectangle, count: usize) -> Vec<Self> {
+        let height = (area.h / count as f32).floor();
+        (0..count)
+            .map(|i| {
+                let y = area.y + height * i as f32;
+                let h = if i + 1 == count {
+                    area.y + area.h - y
+                } else {
+                    height
+                };
+                Self::new(Rectangle::new(area.x, y, area.w, h), Vec2::ZERO)
+            })
+            .collect()
+    }
+
+    /// Moves the camera so `target` is in the center of the viewport.
+    pub fn follow(&mut self, target: Vec2) {
+        self.camera.target = target;
+        self.camera.offset = Vec2::new(self.rect.w, self.rect.h) / 2.;
+    }
+
+    pub fn world_to_screen(&self, point: Vec2) -> Vec2 {
+        self.camera.world_to_screen(point) + Vec2::new(self.rect.x, self.rect.y)
+    }
+
+    pub fn screen_to_world(&self, point: Vec2) -> Vec2 {
+        self.camera
+            .screen_to_world(point - Vec2::new(self.rect.x, self.rect.y))
+    }
+
+    pub fn window(&self) -> Window {
+        Window {
+            camera: Some(self.camera),
+            ..Window::new(self.rect)
+        }
+    }
+}
+
+impl Context {
+    /// Draws everything in `f` in world space, seen through `viewport`'s camera and clipped to it.
+    pub fn draw_viewport<F, R>(&mut self, viewport: &Viewport, f: F) -> R
+    where
+        F: FnOnce(&mut Self) -> R,
+    {
+        self.draw_window(&viewport.window(), f).unwrap()
+    }
+
+    /// Draws the same world once in every viewport, `f` gets the index of the one being drawn.
+    pub fn draw_viewports<F>(&mut self, viewports: &[Viewport], mut f: F)
+    where
+        F: FnMut(&mut Self, usize),
+    {
+        for (i, viewport) in viewports.iter().enumerate() {
+            self.draw_viewport(viewport, |ctx| f(ctx, i));
+        }
+    }
+}
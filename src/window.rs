@@ -0,0 +1,101 @@
+use crate::{Camera2D, Context, Rectangle, Vec2};
+
+/// A part of the screen with its own coordinates, drawn with [`Context::draw_window`].
+/// Inside it `(0, 0)` is its top left corner, the screen size is its size,
+/// and nothing can be drawn outside of it.
+#[derive(Debug, Clone, Copy, PartialEq, Default)]
+pub struct Window {
+    /// Where the window is, in the coordinates of whatever it's drawn in.
+    pub rect: Rectangle,
+    pub visible: bool,
+    /// Used instead of the parent's camera, `None` draws in window coordinates.
+    pub camera: Option<Camera2D>,
+}
+
+impl Window {
+    pub fn new(rect: Rectangle) -> Self {
+        Self {
+            rect,
+            visible: true,
+            camera: None,
+        }
+    }
+
+    pub fn move_to(&mut self, x: f32, y: f32) {
+        self.rect.x = x;
+        self.rect.y = y;
+    }
+
+    pub fn resize(&mut self, width: f32, height: f32) {
+        self.rect.w = width;
+        self.rect.h = height;
+    }
+
+    pub fn show(&mut self) {
+        self.visible = true;
+    }
+
+    pub fn hide(&mut self) {
+        self.visible = false;
+    }
+
+    /// Maps a point from the parent's coordinates to the window's.
+    pub fn local_position(&self, point: Vec2) -> Vec2 {
+        point - Vec2::new(self.rect.x, self.rect.y)
+    }
+}
+
+/// What drawing in a window replaced, to be put back when it's done.
+pub(crate) struct SavedView {
+    origin: Vec2,
+    size: Option<Vec2>,
+    camera: Option<Camera2D>,
+    clip_depth: usize,
+}
+
+impl Context {
+    /// Draws everything in `f` inside `window`, returns `None` without calling `f` if it's hidden.
+    /// Windows can be drawn inside other windows, they are then placed and clipped
+    /// relative to their parent.
+    pub fn draw_window<F, R>(&mut self, window: &Window, f: F) -> Option<R>
+    where
+        F: FnOnce(&mut Self) -> R,
+    {
+        if !self.begin_window(window) {
+            return None;
+        }
+        let result = f(self);
+        self.end_window();
+        Some(result)
+    }
+
+    /// Where `(0, 0)` of the current window is on the screen.
+    pub fn window_origin(&self) -> Vec2 {
+        self.origin
+    }
+
+    pub(crate) fn begin_window(&mut self, window: &Window) -> bool {
+        if !window.visible {
+            return false;
+        }
+        self.windows.push(SavedView {
+            origin: self.origin,
+            size: self.view_size,
+            camera: self.camera.take(),
+            clip_depth: self.clip_stack.len(),
+        });
+        self.push_clip(window.rect);
+        self.origin += Vec2::new(window.rect.x, window.rect.y);
+        self.view_size = Some(Vec2::new(window.rect.w, window.rect.h));
+        self.camera = window.camera;
+        true
+    }
+
+    pub(crate) fn end_window(&mut self) {
+        let saved = self.windows.pop().unwrap();
+        self.origin = saved.origin;
+        self.view_size = saved.size;
+        self.camera = saved.camera;
+        self.clip_stack.truncate(saved.clip_depth);
+    }
+}
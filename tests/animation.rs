@@ -0,0 +1,119 @@
+use std::rc::Rc;
+use teremder::*;
+
+fn sheet() -> Rc<SpriteSheet> {
+    let strip = sprite! {
+        'r' => RED,
+        'g' => GREEN,
+        'b' => BLUE;
+        "rgb",
+    };
+    Rc::new(SpriteSheet::grid(strip, 1., 1.))
+}
+
+fn sequence(animation: &mut AnimatedSprite, steps: usize, dt: f32) -> Vec<usize> {
+    (0..steps)
+        .map(|_| {
+            animation.update(dt);
+            animation.current_frame()
+        })
+        .collect()
+}
+
+#[test]
+fn looping() {
+    let mut animation = AnimatedSprite::new(sheet(), &[0, 1, 2], 0.1);
+    assert_eq!(animation.update(0.05), None);
+    assert_eq!(animation.current_frame(), 0);
+    assert_eq!(animation.update(0.1), None);
+    assert_eq!(animation.current_frame(), 1);
+    assert_eq!(animation.update(0.2), Some(AnimationEvent::Looped));
+    assert_eq!(animation.current_frame(), 0);
+    assert!(animation.is_playing());
+}
+
+#[test]
+fn ping_pong() {
+    let mut animation = AnimatedSprite::new(sheet(), &[0, 1, 2], 1.);
+    animation.set_mode(PlaybackMode::PingPong);
+    assert_eq!(sequence(&mut animation, 6, 1.), [1, 2, 1, 0, 1, 2]);
+
+    animation.restart();
+    assert_eq!(animation.update(3.), None);
+    assert_eq!(animation.update(1.), Some(AnimationEvent::Looped));
+}
+
+#[test]
+fn ping_pong_with_two_frames() {
+    let mut animation = AnimatedSprite::new(sheet(), &[0, 1], 1.);
+    animation.set_mode(PlaybackMode::PingPong);
+    assert_eq!(animation.update(1.), None);
+    assert_eq!(animation.update(1.), Some(AnimationEvent::Looped));
+    assert_eq!(sequence(&mut animation, 4, 1.), [1, 0, 1, 0]);
+}
+
+#[test]
+fn huge_steps_skip_whole_cycles() {
+    let mut animation = AnimatedSprite::new(sheet(), &[0, 1, 2], 1.);
+    assert_eq!(animation.update(3_145_729.), Some(AnimationEvent::Looped));
+    assert_eq!(animation.current_frame(), 1);
+
+    animation.set_mode(PlaybackMode::PingPong);
+    animation.restart();
+    assert_eq!(animation.update(4_194_307.), Some(AnimationEvent::Looped));
+    assert_eq!(animation.current_frame(), 1);
+    assert_eq!(animation.update(1e30), Some(AnimationEvent::Looped));
+}
+
+#[test]
+fn once_stops_on_the_last_frame() {
+    let frames = vec![
+        Frame {
+            region: 2,
+            duration: 0.5,
+        },
+        Frame {
+            region: 0,
+            duration: 1.,
+        },
+    ];
+    let mut animation = AnimatedSprite::with_frames(sheet(), frames);
+    animation.set_mode(PlaybackMode::Once);
+    animation.set_speed(2.);
+    assert_eq!(animation.update(0.25), None);
+    assert_eq!(animation.current_frame(), 1);
+    assert_eq!(animation.update(10.), Some(AnimationEvent::Finished));
+    assert!(animation.is_finished() && !animation.is_playing());
+    assert_eq!(animation.current_frame(), 1);
+    assert_eq!(animation.update(10.), None);
+
+    animation.restart();
+    assert_eq!(animation.current_frame(), 0);
+    assert!(!animation.is_finished());
+}
+
+#[test]
+fn paused_and_empty_animations_stay_put() {
+    let mut animation = AnimatedSprite::new(sheet(), &[0, 1], 0.1);
+    animation.pause();
+    assert_eq!(sequence(&mut animation, 3, 1.), [0, 0, 0]);
+
+    let mut empty = AnimatedSprite::new(sheet(), &[], 0.1);
+    assert_eq!(empty.update(1.), None);
+    let mut instant = AnimatedSprite::new(sheet(), &[0, 1], 0.);
+    assert_eq!(instant.update(1.), None);
+}
+
+#[test]
+fn draws_the_current_frame() {
+    let mut ctx = Context::headless(4, 2);
+    let mut animation = AnimatedSprite::new(sheet(), &[2, 1], 0.1);
+    animation.draw_with_ctx(&mut ctx, 0., 0.);
+    animation.update(0.1);
+    animation.draw_with_ctx(&mut ctx, 1., 0.);
+    assert_eq!(ctx.get_pixel(0., 0.), BLUE);
+    assert_eq!(ctx.get_pixel(1., 0.), GREEN);
+
+    ctx.next_frame();
+    assert!(ctx.get_frame_time() >= 0.);
+}
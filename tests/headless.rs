@@ -0,0 +1,234 @@
+use crossterm::event::{
+    Event, KeyCode, KeyEvent, KeyEventKind, KeyModifiers, MouseButton as CtMouseButton, MouseEvent,
+    MouseEventKind,
+};
+use teremder::*;
+
+fn key(code: KeyCode, kind: KeyEventKind) -> Event {
+    Event::Key(KeyEvent::new_with_kind(code, KeyModifiers::NONE, kind))
+}
+
+fn mouse(kind: MouseEventKind, column: u16, row: u16) -> Event {
+    Event::Mouse(MouseEvent {
+        kind,
+        column,
+        row,
+        modifiers: KeyModifiers::NONE,
+    })
+}
+
+#[test]
+fn size_comes_from_backend() {
+    let ctx = Context::headless(20, 10);
+    assert_eq!(ctx.screen_width(), 20.);
+    assert_eq!(ctx.screen_height(), 20.);
+}
+
+#[test]
+fn drawing_can_be_read_back() {
+    let mut ctx = Context::headless(8, 4);
+    ctx.clear_background(BLACK);
+    ctx.fill_rect(2., 2., 3., 3., RED);
+    ctx.next_frame();
+
+    assert_eq!(ctx.get_pixel(2., 2.), RED);
+    assert_eq!(ctx.get_pixel(4., 4.), RED);
+    assert_eq!(ctx.get_pixel(5., 5.), BLACK);
+    assert_eq!(
+        ctx.display_buffer().get(3, 3),
+        ctx.drawing_buffer().get(3, 3)
+    );
+}
+
+#[test]
+fn pushed_key_events_are_handled_next_frame() {
+    let mut ctx = Context::headless(8, 4);
+    ctx.push_event(key(KeyCode::Char('a'), KeyEventKind::Press));
+    assert!(!ctx.is_key_down(Key::A));
+
+    ctx.next_frame();
+    assert!(ctx.is_key_down(Key::A));
+    assert!(ctx.is_key_pressed(Key::A));
+
+    ctx.next_frame();
+    assert!(ctx.is_key_down(Key::A));
+    assert!(!ctx.is_key_pressed(Key::A));
+
+    ctx.push_event(key(KeyCode::Char('a'), KeyEventKind::Release));
+    ctx.next_frame();
+    assert!(ctx.is_key_released(Key::A));
+}
+
+#[test]
+fn pushed_mouse_events_are_handled_next_frame() {
+    let mut ctx = Context::headless(8, 4);
+    ctx.push_event(mouse(MouseEventKind::Moved, 3, 1));
+    ctx.push_event(mouse(MouseEventKind::Down(CtMouseButton::Left), 3, 1));
+    ctx.next_frame();
+
+    assert_eq!(ctx.mouse_position(), (3., 2.));
+    assert!(ctx.is_mouse_button_pressed(MouseButton::Left));
+}
+
+#[test]
+fn virtual_resolution_is_letterboxed_and_maps_the_mouse() {
+    let mut ctx = Context::headless(16, 8);
+    ctx.set_virtual_resolution(8., 4., ScaleMode::Integer);
+    assert_eq!((ctx.screen_width(), ctx.screen_height()), (8., 4.));
+
+    ctx.clear_background(RED);
+    ctx.set_pixel(7., 3., WHITE);
+    ctx.push_event(mouse(MouseEventKind::Moved, 4, 3));
+    ctx.next_frame();
+
+    let display = ctx.display_buffer();
+    assert_eq!((display.width(), display.height()), (16, 16));
+    let color = |x, y| {
+        let p = display.get(x, y);
+        Color::rgb(p.r, p.g, p.b)
+    };
+    assert_eq!(color(0, 3), BLACK);
+    assert_eq!(color(0, 4), RED);
+    assert_eq!(color(15, 11), WHITE);
+    assert_eq!(color(14, 10), WHITE);
+    assert_eq!(color(13, 11), RED);
+    assert_eq!(color(15, 12), BLACK);
+    assert_eq!(ctx.mouse_position(), (2., 1.));
+
+    ctx.push_event(Event::Resize(40, 10));
+    ctx.next_frame();
+    assert_eq!((ctx.screen_width(), ctx.screen_height()), (8., 4.));
+    assert_eq!(ctx.display_buffer().width(), 40);
+}
+
+#[test]
+fn mouse_position_is_relative_to_the_window() {
+    let mut ctx = Context::headless(8, 4);
+    ctx.push_event(mouse(MouseEventKind::Moved, 5, 3));
+    ctx.next_frame();
+
+    let window = Window::new(rect!(2, 4, 4, 4));
+    let local = ctx.draw_window(&window, |ctx| ctx.mouse_position());
+    assert_eq!(local, Some((3., 2.)));
+    assert_eq!(window.local_position(vec2!(5, 6)), vec2!(3, 2));
+    assert_eq!(ctx.mouse_position(), (5., 6.));
+}
+
+#[test]
+fn lines_far_off_screen_are_clipped() {
+    let mut ctx = Context::headless(8, 4);
+    ctx.clear_background(BLACK);
+    ctx.draw_line(-1e10, 1., 1e10, 1., RED);
+    ctx.draw_line_aa(0., 3., 3e7, 3., RED);
+    ctx.draw_line_aa(0., -0.3, 7., -0.3, RED);
+    ctx.next_frame();
+
+    assert_eq!(ctx.get_pixel(0., 1.), RED);
+    assert_eq!(ctx.get_pixel(7., 1.), RED);
+    assert_eq!(ctx.get_pixel(4., 3.), RED);
+    assert_ne!(ctx.get_pixel(3., 0.), BLACK);
+
+    let mut empty = Context::headless(0, 0);
+    empty.draw_line(0., 0., 5., 5., RED);
+    empty.draw_line_aa(0., 0., 5., 5., RED);
+}
+
+#[test]
+fn drawing_on_an_empty_screen_does_nothing() {
+    let mut ctx = Context::headless(0, 0);
+    ctx.draw_ellipse(2., 2., 3., 2., 1., RED);
+    ctx.fill_pie(2., 2., 3., 0., 1., RED);
+    ctx.draw_arc(2., 2., 3., 0., 1., 1., RED);
+    ctx.next_frame();
+    assert_eq!(ctx.display_buffer().width(), 0);
+}
+
+#[test]
+fn huge_fills_only_visit_visible_rows() {
+    let mut ctx = Context::headless(8, 4);
+    ctx.clear_background(BLACK);
+    ctx.fill_circle(5., 5., 1e8, RED);
+    ctx.fill_triangle(0., -1e9, 1e9, 1e9, -1e9, 1e9, BLUE);
+    ctx.next_frame();
+    assert_eq!(ctx.get_pixel(0., 0.), BLUE);
+    assert_eq!(ctx.get_pixel(7., 7.), BLUE);
+}
+
+#[test]
+fn pies_wrap_around_when_the_end_angle_is_smaller() {
+    use std::f32::consts::PI;
+
+    let mut ctx = Context::headless(8, 4);
+    ctx.clear_background(BLACK);
+    ctx.fill_rounded_rect(-1e9, 0., 2e9, 1e9, 1e8, BLUE);
+    // clockwise from the bottom through the left to the top
+    ctx.fill_pie(4., 4., 4., PI / 2., -PI / 2., RED);
+    ctx.next_frame();
+    assert_eq!(ctx.get_pixel(1., 4.), RED);
+    assert_eq!(ctx.get_pixel(6., 4.), BLUE);
+}
+
+#[test]
+fn huge_rects_are_clipped() {
+    let mut ctx = Context::headless(8, 4);
+    ctx.clear_background(BLACK);
+    ctx.fill_rect(0., 0., 5., f32::MAX, RED);
+    ctx.fill_rect(0., 2e9, 5., 2e9, BLUE);
+    ctx.fill_rect(-3., 0., 5., 1., GREEN);
+    ctx.next_frame();
+    assert_eq!(ctx.get_pixel(4., 7.), RED);
+    assert_eq!(ctx.get_pixel(5., 7.), BLACK);
+    assert_eq!(ctx.get_pixel(1., 0.), GREEN);
+    assert_eq!(ctx.get_pixel(2., 0.), RED);
+}
+
+#[test]
+fn hidden_layers_are_skipped() {
+    let mut ctx = Context::headless(8, 4);
+    ctx.clear_background(BLACK);
+    ctx.with_layer(1, |ctx| ctx.fill_rect(0., 0., 4., 4., RED));
+    ctx.set_layer_visible(1, false);
+    ctx.with_layer(2, |ctx| ctx.fill_rect(0., 0., 4., 4., BLUE));
+    ctx.set_layer_opacity(2, 0.);
+    ctx.next_frame();
+    assert_eq!(ctx.get_pixel(1., 1.), BLACK);
+}
+
+#[test]
+fn reading_outside_the_screen_is_blank() {
+    let mut ctx = Context::headless(4, 1);
+    ctx.clear_background(RED);
+    ctx.next_frame();
+    assert_eq!(ctx.get_pixel(3., 1.), RED);
+    assert_eq!(ctx.get_pixel(5., 0.), BLANK);
+    assert_eq!(ctx.get_pixel(100., 100.), BLANK);
+    assert_eq!(ctx.get_pixel(-1., 0.), BLANK);
+    assert_eq!(ctx.get_pixel(f32::NAN, 0.), BLANK);
+
+    let window = Window::new(rect!(1, 0, 2, 2));
+    ctx.draw_window(&window, |ctx| {
+        assert_eq!(ctx.get_pixel(1., 1.), RED);
+        assert_eq!(ctx.get_pixel(2., 0.), BLANK);
+    });
+    let sprite = Sprite::new(2., 2.);
+    assert_eq!(sprite.get_pixel(2., 0.), BLANK);
+    assert_eq!(sprite.get_pixel(0., -1.), BLANK);
+}
+
+#[test]
+fn single_point_subpaths_are_not_stroked() {
+    let mut ctx = Context::headless(8, 4);
+    ctx.clear_background(BLACK);
+    let mut path = Path::new();
+    path.move_to(vec2!(1, 1))
+        .move_to(vec2!(1, 5))
+        .line_to(vec2!(6, 5))
+        .move_to(vec2!(6, 1))
+        .close();
+    assert_eq!(path.flatten(0.5).len(), 1);
+    ctx.stroke_path(&path, 2., WHITE);
+    ctx.next_frame();
+    assert_eq!(ctx.get_pixel(3., 5.), WHITE);
+    assert_eq!(ctx.get_pixel(1., 1.), BLACK);
+    assert_eq!(ctx.get_pixel(6., 1.), BLACK);
+}
@@ -0,0 +1,565 @@
+use teremder::{testing::assert_snapshot, *};
+
+fn ctx() -> Context {
+    let mut ctx = Context::headless(16, 8);
+    ctx.clear_background(BLACK);
+    ctx
+}
+
+#[test]
+fn fill_rect() {
+    let mut ctx = ctx();
+    ctx.fill_rect(2., 3., 6., 4., RED);
+    ctx.fill_rect(12., 12., 10., 10., BLUE);
+    ctx.next_frame();
+    assert_snapshot(&ctx, "tests/snapshots/fill_rect.txt");
+}
+
+#[test]
+fn fill_circle() {
+    let mut ctx = ctx();
+    ctx.fill_circle(8., 8., 6., GREEN);
+    ctx.fill_circle(1., 1., 2.5, RED);
+    ctx.next_frame();
+    assert_snapshot(&ctx, "tests/snapshots/fill_circle.txt");
+}
+
+#[test]
+fn fill_triangle() {
+    let mut ctx = ctx();
+    ctx.fill_triangle(1., 1., 14., 4., 5., 14., YELLOW);
+    ctx.next_frame();
+    assert_snapshot(&ctx, "tests/snapshots/fill_triangle.txt");
+}
+
+#[test]
+fn sprite_draw_with_ctx() {
+    let mut ctx = ctx();
+    let mut sprite = Sprite::new(4., 3.);
+    for x in 0..4 {
+        sprite.set_pixel(x as f32, 0., WHITE);
+        sprite.set_pixel(x as f32, 2., BLUE);
+    }
+    sprite.set_pixel(0., 1., RED);
+    sprite.set_pixel(3., 1., RED);
+    sprite.draw_with_ctx(&mut ctx, 2., 3.);
+    sprite.draw_with_ctx(&mut ctx, 14., 14.);
+    ctx.next_frame();
+    assert_snapshot(&ctx, "tests/snapshots/sprite_draw_with_ctx.txt");
+}
+
+#[test]
+fn diff_points_at_mismatched_pixels() {
+    let mut ctx = ctx();
+    ctx.fill_rect(0., 0., 2., 1., RED);
+    let expected = testing::decode(&testing::encode(ctx.drawing_buffer())).unwrap();
+    assert!(testing::diff(&expected, ctx.drawing_buffer()).is_none());
+
+    ctx.set_pixel(3., 1., WHITE);
+    let diff = testing::diff(&expected, ctx.drawing_buffer()).unwrap();
+    assert!(diff.starts_with("1 pixels differ"));
+    assert!(diff.contains("(3, 1): expected 000000, got ffffff"));
+}
+
+#[test]
+fn alpha_blending() {
+    let mut ctx = ctx();
+    ctx.clear_background(BLUE);
+    ctx.fill_rect(2., 2., 8., 8., Color::rgba(255, 255, 255, 128));
+    ctx.fill_circle(10., 10., 4., Color::rgba(230, 41, 55, 64));
+    ctx.fill_triangle(0., 15., 15., 15., 8., 11., Color::rgba(0, 0, 0, 0));
+
+    let mut sprite = Sprite::new(3., 3.);
+    sprite.set_pixel(1., 0., YELLOW);
+    sprite.set_pixel(0., 1., YELLOW);
+    sprite.set_pixel(2., 1., Color::rgba(253, 249, 0, 128));
+    sprite.set_pixel(1., 2., YELLOW);
+    sprite.draw_with_ctx(&mut ctx, 12., 1.);
+
+    ctx.next_frame();
+    assert_snapshot(&ctx, "tests/snapshots/alpha_blending.txt");
+}
+
+#[test]
+fn blend_modes() {
+    let mut ctx = ctx();
+    ctx.clear_background(Color::rgb(100, 100, 100));
+    let modes = [
+        BlendMode::Alpha,
+        BlendMode::Additive,
+        BlendMode::Multiply,
+        BlendMode::Screen,
+        BlendMode::Subtract,
+        BlendMode::Replace,
+    ];
+    for (i, mode) in modes.into_iter().enumerate() {
+        let y = i as f32 * 2.;
+        ctx.with_blend_mode(mode, |ctx| {
+            ctx.fill_rect(0., y, 8., 2., Color::rgb(200, 50, 0));
+            ctx.fill_rect(8., y, 8., 2., Color::rgba(200, 50, 0, 128));
+        });
+    }
+    assert_eq!(ctx.blend_mode(), BlendMode::Alpha);
+    ctx.next_frame();
+    assert_snapshot(&ctx, "tests/snapshots/blend_modes.txt");
+}
+
+#[test]
+fn lines() {
+    let mut ctx = ctx();
+    ctx.draw_line(0., 0., 15., 5., WHITE);
+    ctx.draw_line(15., 0., 12., 15., RED);
+    ctx.draw_line_thick(1., 8., 9., 13., 3., GREEN);
+    ctx.draw_line_aa(0., 15., 10., 6., YELLOW);
+    ctx.next_frame();
+    assert_snapshot(&ctx, "tests/snapshots/lines.txt");
+}
+
+#[test]
+fn outlines() {
+    let mut ctx = Context::headless(32, 16);
+    ctx.clear_background(BLACK);
+    ctx.draw_rect(1., 1., 10., 8., 1., WHITE);
+    ctx.draw_rect(13., 1., 8., 8., 2., RED);
+    ctx.draw_circle(26., 6., 5., 1., GREEN);
+    ctx.draw_ellipse(8., 20., 7., 4., 2., BLUE);
+    ctx.draw_triangle(18., 14., 30., 16., 20., 28., 1., YELLOW);
+    let points = [vec2!(2, 26), vec2!(8, 30), vec2!(14, 26), vec2!(8, 31)];
+    ctx.draw_polygon(&points, 1., PURPLE);
+    ctx.next_frame();
+    assert_snapshot(&ctx, "tests/snapshots/outlines.txt");
+}
+
+#[test]
+fn fill_shapes_partly_off_screen() {
+    let mut ctx = ctx();
+    ctx.fill_triangle(-20., -4., 30., 6., 4., 40., RED);
+    ctx.fill_circle(16., 16., 6., GREEN);
+    ctx.fill_triangle(1., 1., 5., 5., 9., 9., WHITE);
+    ctx.next_frame();
+    assert_snapshot(&ctx, "tests/snapshots/fill_shapes_partly_off_screen.txt");
+}
+
+#[test]
+fn curved_shapes() {
+    use std::f32::consts::PI;
+
+    let mut ctx = Context::headless(32, 16);
+    ctx.clear_background(BLACK);
+    ctx.fill_ellipse(8., 6., 7., 4., BLUE);
+    ctx.draw_arc(24., 8., 7., -PI / 2., PI, 2., GREEN);
+    ctx.fill_pie(8., 22., 7., 0., PI / 2., RED);
+    ctx.fill_rounded_rect(17., 18., 13., 12., 4., GRAY);
+    ctx.draw_rounded_rect(19., 20., 9., 8., 3., 1., WHITE);
+    ctx.next_frame();
+    assert_snapshot(&ctx, "tests/snapshots/curved_shapes.txt");
+}
+
+#[test]
+fn fill_polygon() {
+    let mut ctx = Context::headless(32, 16);
+    ctx.clear_background(BLACK);
+    let rock = [
+        vec2!(2, 2),
+        vec2!(12, 1),
+        vec2!(9, 6),
+        vec2!(14, 12),
+        vec2!(6, 10),
+        vec2!(1, 14),
+    ];
+    ctx.fill_polygon(&rock, BROWN);
+
+    let star = |cx: f32, cy: f32| {
+        (0..5)
+            .map(|i| Vec2::angled(i as f32 * 4. * std::f32::consts::PI / 5.) * 7. + vec2!(cx, cy))
+            .collect::<Vec<_>>()
+    };
+    ctx.fill_polygon(&star(24., 8.), YELLOW);
+    ctx.fill_polygon_with_rule(&star(16., 23.), FillRule::EvenOdd, YELLOW);
+    ctx.draw_polygon(&rock, 1., WHITE);
+    ctx.next_frame();
+    assert_snapshot(&ctx, "tests/snapshots/fill_polygon.txt");
+}
+
+#[test]
+fn paths() {
+    let mut ctx = Context::headless(32, 16);
+    ctx.clear_background(BLACK);
+    ctx.draw_bezier_quad(vec2!(1, 14), vec2!(8, -6), vec2!(15, 14), 1., WHITE);
+    ctx.draw_bezier_cubic(
+        vec2!(17, 2),
+        vec2!(40, 2),
+        vec2!(10, 14),
+        vec2!(30, 14),
+        2.,
+        RED,
+    );
+
+    let mut drop = Path::new();
+    drop.move_to(vec2!(8, 17))
+        .quad_to(vec2!(14, 24), vec2!(14, 27))
+        .cubic_to(vec2!(14, 32), vec2!(2, 32), vec2!(2, 27))
+        .quad_to(vec2!(2, 24), vec2!(8, 17))
+        .close();
+    ctx.fill_path(&drop, SKYBLUE);
+    ctx.stroke_path(&drop, 1., BLUE);
+
+    let mut ring = Path::new();
+    ring.set_fill_rule(FillRule::EvenOdd)
+        .move_to(vec2!(18, 18))
+        .line_to(vec2!(30, 18))
+        .line_to(vec2!(30, 30))
+        .line_to(vec2!(18, 30))
+        .close()
+        .move_to(vec2!(21, 21))
+        .line_to(vec2!(27, 21))
+        .line_to(vec2!(27, 27))
+        .line_to(vec2!(21, 27));
+    ring.fill(GREEN).draw_with_ctx(&mut ctx);
+    let subpaths = ring.flatten(0.5);
+    assert_eq!(subpaths.len(), 2);
+    assert_eq!(subpaths[1].0[0], vec2!(21, 21));
+    ctx.next_frame();
+    assert_snapshot(&ctx, "tests/snapshots/paths.txt");
+}
+
+#[test]
+fn drawable_shapes() {
+    let mut ctx = ctx();
+    rect!(1, 1, 8, 6)
+        .fill(RED)
+        .outline(WHITE, 1.)
+        .draw_with_ctx(&mut ctx);
+    circle!(12, 4, 3).fill(GREEN).draw_with_ctx(&mut ctx);
+    Triangle::new(vec2!(1, 14), vec2!(7, 9), vec2!(8, 15))
+        .outline(YELLOW, 1.)
+        .draw_with_ctx(&mut ctx);
+    LineSegment::new(vec2!(10, 9), vec2!(15, 14))
+        .outline(BLUE, 2.)
+        .draw_with_ctx(&mut ctx);
+    vec2!(14, 9).fill(WHITE).draw_with_ctx(&mut ctx);
+    ctx.next_frame();
+    assert_snapshot(&ctx, "tests/snapshots/drawable_shapes.txt");
+}
+
+#[test]
+fn gradients() {
+    let mut ctx = Context::headless(32, 16);
+    ctx.clear_background(Paint::linear(
+        vec2!(0, 0),
+        vec2!(0, 31),
+        &[(0., DARKBLUE), (1., SKYBLUE)],
+    ));
+    ctx.fill_circle(
+        8.,
+        8.,
+        7.,
+        Paint::radial(vec2!(8, 8), 7., &[(0., WHITE), (0.5, YELLOW), (1., ORANGE)]),
+    );
+    rect!(17, 1, 14, 14)
+        .fill(Paint::conic(
+            vec2!(24, 8),
+            0.,
+            &[(0., RED), (0.5, GREEN), (1., RED)],
+        ))
+        .draw_with_ctx(&mut ctx);
+    ctx.fill_rounded_rect(
+        2.,
+        18.,
+        28.,
+        12.,
+        4.,
+        Paint::linear(
+            vec2!(2, 0),
+            vec2!(30, 0),
+            &[(0., Color::rgba(0, 0, 0, 0)), (1., BLACK)],
+        ),
+    );
+    ctx.next_frame();
+    assert_snapshot(&ctx, "tests/snapshots/gradients.txt");
+}
+
+#[test]
+fn clipping() {
+    let mut ctx = ctx();
+    ctx.push_clip(rect!(2, 2, 10, 10));
+    ctx.clear_background(DARKGRAY);
+    ctx.fill_circle(2., 2., 5., RED);
+    ctx.with_clip(rect!(6, 6, 10, 10), |ctx| {
+        ctx.fill_rect(0., 0., 16., 16., GREEN);
+        ctx.draw_line(0., 15., 15., 0., WHITE);
+    });
+    let mut sprite = Sprite::new(4., 4.);
+    for i in 0..4 {
+        sprite.set_pixel(i as f32, i as f32, YELLOW);
+    }
+    sprite.draw_with_ctx(&mut ctx, 10., 1.);
+    assert_eq!(ctx.pop_clip(), Some(rect!(2, 2, 10, 10)));
+    assert_eq!(ctx.pop_clip(), None);
+    ctx.set_pixel(0., 0., BLUE);
+    ctx.next_frame();
+    assert_snapshot(&ctx, "tests/snapshots/clipping.txt");
+}
+
+#[test]
+fn camera() {
+    let mut ctx = Context::headless(32, 16);
+    ctx.clear_background(BLACK);
+    let camera = Camera2D {
+        target: vec2!(100, 100),
+        offset: vec2!(16, 16),
+        zoom: 2.,
+        rotation: std::f32::consts::FRAC_PI_6,
+    };
+    ctx.set_camera(camera);
+    assert_eq!(ctx.world_to_screen(vec2!(100, 100)), vec2!(16, 16));
+    let back = ctx.screen_to_world(ctx.world_to_screen(vec2!(93, 104)));
+    assert!(back.distance(&vec2!(93, 104)) < 1e-4);
+
+    ctx.fill_rect(92., 92., 6., 4., RED);
+    ctx.fill_circle(104., 96., 2., GREEN);
+    ctx.draw_line(92., 108., 108., 108., WHITE);
+    ctx.fill_ellipse(104., 104., 3., 1.5, YELLOW);
+    let mut sprite = Sprite::new(2., 2.);
+    sprite.set_pixel(0., 0., BLUE);
+    sprite.set_pixel(1., 1., BLUE);
+    sprite.draw_with_ctx(&mut ctx, 96., 101.);
+
+    ctx.set_default_camera();
+    ctx.set_pixel(0., 0., WHITE);
+    ctx.next_frame();
+    assert_snapshot(&ctx, "tests/snapshots/camera.txt");
+}
+
+#[test]
+fn stroked_paths_under_camera() {
+    let mut ctx = ctx();
+    ctx.set_camera(Camera2D::new(vec2!(100, 100), vec2!(8, 8)));
+    let mut zigzag = Path::new();
+    zigzag
+        .move_to(vec2!(93, 94))
+        .line_to(vec2!(97, 98))
+        .line_to(vec2!(101, 94));
+    ctx.stroke_path(&zigzag, 1., WHITE);
+    ctx.draw_bezier_quad(vec2!(93, 103), vec2!(97, 99), vec2!(101, 103), 1., RED);
+    ctx.draw_bezier_cubic(
+        vec2!(102, 93),
+        vec2!(108, 93),
+        vec2!(102, 99),
+        vec2!(106, 99),
+        1.,
+        GREEN,
+    );
+    vec2!(105, 103).outline(YELLOW, 3.).draw_with_ctx(&mut ctx);
+    ctx.next_frame();
+    assert_snapshot(&ctx, "tests/snapshots/stroked_paths_under_camera.txt");
+}
+
+#[test]
+fn stroked_paths_in_window() {
+    let mut ctx = ctx();
+    let window = Window::new(rect!(4, 4, 10, 4));
+    ctx.draw_window(&window, |ctx| {
+        let mut line = Path::new();
+        line.move_to(vec2!(0, 1)).line_to(vec2!(9, 1));
+        line.outline(WHITE, 1.).draw_with_ctx(ctx);
+        ctx.draw_bezier_quad(vec2!(0, 3), vec2!(4, 1), vec2!(9, 3), 1., RED);
+    });
+    ctx.next_frame();
+    assert_snapshot(&ctx, "tests/snapshots/stroked_paths_in_window.txt");
+}
+
+#[test]
+fn draw_to_sprite() {
+    let mut ctx = ctx();
+    ctx.set_camera(Camera2D {
+        offset: vec2!(4, 4),
+        ..Default::default()
+    });
+    ctx.push_clip(rect!(0, 0, 8, 8));
+    let mut sprite = Sprite::new(6., 6.);
+    ctx.draw_to_sprite(&mut sprite, |ctx| {
+        assert_eq!((ctx.screen_width(), ctx.screen_height()), (6., 6.));
+        ctx.fill_circle(3., 3., 3., RED);
+        ctx.fill_rect(0., 0., 3., 3., Color::rgba(0, 0, 255, 128));
+    });
+    ctx.pop_clip();
+    ctx.set_default_camera();
+
+    assert_eq!(sprite.get_pixel(0., 5.), Color::rgba(0, 0, 0, 0));
+    assert_eq!(sprite.get_pixel(0., 0.).a, 128);
+    sprite.draw_with_ctx(&mut ctx, 1., 1.);
+    sprite.draw_with_ctx(&mut ctx, 9., 9.);
+    ctx.next_frame();
+    assert_snapshot(&ctx, "tests/snapshots/draw_to_sprite.txt");
+}
+
+#[test]
+fn layers() {
+    let mut ctx = ctx();
+    const HUD: u32 = 2;
+    const PARTICLES: u32 = 1;
+    ctx.with_layer(HUD, |ctx| ctx.fill_rect(0., 0., 16., 3., DARKGRAY));
+    ctx.set_layer(PARTICLES);
+    ctx.fill_circle(8., 8., 5., Color::rgba(255, 255, 255, 128));
+    ctx.fill_circle(4., 1., 2., YELLOW);
+    ctx.set_layer(0);
+    ctx.fill_rect(2., 2., 12., 12., RED);
+    ctx.set_layer_blend_mode(PARTICLES, BlendMode::Additive);
+    ctx.set_layer_opacity(HUD, 0.5);
+
+    let mut hidden = Context::headless(16, 8);
+    hidden.with_layer(3, |ctx| ctx.clear_background(GREEN));
+    hidden.set_layer_visible(3, false);
+    hidden.next_frame();
+    assert!(testing::diff(hidden.drawing_buffer(), hidden.display_buffer()).is_none());
+
+    // the screen layer only has what was drawn on it
+    assert_eq!(ctx.get_pixel(8., 8.), RED);
+    ctx.next_frame();
+    testing::assert_buffer_snapshot(ctx.display_buffer(), "tests/snapshots/layers.txt");
+}
+
+#[test]
+fn windows() {
+    let mut ctx = ctx();
+    let mut window = Window::new(rect!(2, 2, 10, 8));
+    let inner = Window {
+        camera: Some(Camera2D::new(vec2!(50, 50), vec2!(3, 3))),
+        ..Window::new(rect!(6, 4, 8, 8))
+    };
+    window.move_to(3., 4.);
+    let drawn = ctx.draw_window(&window, |ctx| {
+        assert_eq!((ctx.screen_width(), ctx.screen_height()), (10., 8.));
+        ctx.clear_background(DARKGRAY);
+        ctx.draw_line(0., 0., 20., 20., WHITE);
+        ctx.draw_window(&inner, |ctx| {
+            assert_eq!(ctx.window_origin(), vec2!(9, 8));
+            ctx.fill_circle(50., 50., 3., RED);
+        });
+        ctx.get_pixel(1., 1.)
+    });
+    assert_eq!(drawn, Some(WHITE));
+    window.hide();
+    assert_eq!(ctx.draw_window(&window, |_| ()), None);
+    ctx.fill_rect(0., 0., 2., 2., BLUE);
+    ctx.next_frame();
+    assert_snapshot(&ctx, "tests/snapshots/windows.txt");
+}
+
+#[test]
+fn split_screen() {
+    let mut ctx = ctx();
+    let mut viewports = Viewport::side_by_side(rect!(0, 0, 16, 16), 2);
+    assert_eq!(viewports[1].rect, rect!(8, 0, 8, 16));
+    viewports[0].follow(vec2!(10, 10));
+    viewports[1].follow(vec2!(20, 12));
+    viewports[1].camera.zoom = 2.;
+    assert_eq!(viewports[1].world_to_screen(vec2!(20, 12)), vec2!(12, 8));
+    assert_eq!(viewports[1].screen_to_world(vec2!(12, 8)), vec2!(20, 12));
+
+    ctx.draw_viewports(&viewports, |ctx, i| {
+        ctx.clear_background(if i == 0 { DARKGRAY } else { GRAY });
+        ctx.fill_rect(8., 8., 4., 4., RED);
+        ctx.fill_circle(20., 12., 1., GREEN);
+    });
+    ctx.next_frame();
+    assert_snapshot(&ctx, "tests/snapshots/split_screen.txt");
+}
+
+#[test]
+fn sprite_sheet() {
+    let mut ctx = ctx();
+    let tiles = sprite! {
+        'r' => RED,
+        'g' => GREEN,
+        'b' => BLUE,
+        'w' => WHITE;
+        "rrggb",
+        "rwgwb",
+        "bbwwb",
+    };
+    let manifest = "// name x y width height\nwall 0 0 2 2\n\nfloor 2 0 2 2\n";
+    let sheet = SpriteSheet::with_manifest(tiles, manifest).unwrap();
+    assert_eq!(sheet.named("floor"), Some(rect!(2, 0, 2, 2)));
+    assert!(SpriteSheet::with_manifest(Sprite::new(1., 1.), "wall 0 0 2").is_err());
+
+    sheet.draw_named_with_ctx(&mut ctx, "wall", 1., 1.);
+    sheet.draw_named_with_ctx(&mut ctx, "floor", 4., 1.);
+    let sprite = sheet.sprite();
+    sprite.draw_region_with_ctx(&mut ctx, rect!(3, 1, 4, 4), vec2!(8, 1));
+    ctx.set_camera(Camera2D {
+        zoom: 2.,
+        ..Default::default()
+    });
+    sprite.draw_region_with_ctx(&mut ctx, rect!(1, 1, 3, 2), vec2!(1, 3));
+    ctx.set_default_camera();
+
+    let grid = SpriteSheet::grid(
+        Sprite::from_text("a ff0000\nb 0000ff\n---\nab\nba\nab").unwrap(),
+        1.,
+        2.,
+    );
+    assert_eq!(grid.len(), 2);
+    let mut named = SpriteSheet::new(Sprite::new(4., 4.));
+    assert_eq!(named.insert("a", rect!(0, 0, 1, 1)), 0);
+    assert_eq!(named.insert("b", rect!(1, 0, 1, 1)), 1);
+    assert_eq!(named.insert("a", rect!(2, 2, 2, 2)), 0);
+    assert_eq!(named.len(), 2);
+    assert_eq!(named.named("a"), Some(rect!(2, 2, 2, 2)));
+    for (w, h) in [
+        (0., 1.),
+        (1., -2.),
+        (f32::NAN, 1.),
+        (1., f32::INFINITY),
+        (1e-30, 1.),
+    ] {
+        assert!(SpriteSheet::grid(Sprite::new(4., 4.), w, h).is_empty());
+    }
+    grid.draw_with_ctx(&mut ctx, 1, 14., 14.);
+    ctx.next_frame();
+    assert_snapshot(&ctx, "tests/snapshots/sprite_sheet.txt");
+}
+
+#[test]
+fn sprite_draw_ex() {
+    let mut ctx = ctx();
+    let arrow = sprite! {
+        '.' => BLANK,
+        'r' => RED,
+        'w' => WHITE;
+        "rw.",
+        "rrw",
+        "rw.",
+    };
+
+    let mut plain = Context::headless(4, 2);
+    arrow.draw_ex_with_ctx(&mut plain, 1., 0., &DrawSpriteParams::default());
+    let mut expected = Context::headless(4, 2);
+    arrow.draw_with_ctx(&mut expected, 1., 0.);
+    assert!(testing::diff(expected.drawing_buffer(), plain.drawing_buffer()).is_none());
+
+    let stretched = DrawSpriteParams {
+        scale: vec2!(2, 1),
+        flip_x: true,
+        ..Default::default()
+    };
+    arrow.draw_ex_with_ctx(&mut ctx, 0., 0., &stretched);
+    let rotated = DrawSpriteParams {
+        rotation: std::f32::consts::FRAC_PI_2,
+        pivot: Some(vec2!(0, 0)),
+        source: Some(rect!(0, 0, 2, 3)),
+        ..Default::default()
+    };
+    arrow.draw_ex_with_ctx(&mut ctx, 12., 0., &rotated);
+    let smooth = DrawSpriteParams {
+        scale: vec2!(3, 3),
+        rotation: 0.3,
+        filter: Filter::Bilinear,
+        flip_y: true,
+        ..Default::default()
+    };
+    arrow.draw_ex_with_ctx(&mut ctx, 3., 5., &smooth);
+    ctx.next_frame();
+    assert_snapshot(&ctx, "tests/snapshots/sprite_draw_ex.txt");
+}
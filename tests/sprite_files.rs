@@ -0,0 +1,185 @@
+use teremder::*;
+
+fn sprite() -> Sprite {
+    let mut sprite = Sprite::new(3., 2.);
+    sprite.set_pixel(0., 0., RED);
+    sprite.set_pixel(1., 0., Color::rgba(10, 20, 30, 128));
+    sprite.set_pixel(2., 1., WHITE);
+    sprite
+}
+
+fn pixels(sprite: &Sprite) -> Vec<Color> {
+    let mut pixels = Vec::new();
+    for y in 0..sprite.height() as usize {
+        for x in 0..sprite.width() as usize {
+            pixels.push(sprite.get_pixel(x as f32, y as f32));
+        }
+    }
+    pixels
+}
+
+#[test]
+fn png_and_bmp_round_trip_with_alpha() {
+    for format in [ImageFormat::Png, ImageFormat::Bmp] {
+        let bytes = sprite().to_bytes(format).unwrap();
+        assert_eq!(ImageFormat::from_bytes(&bytes), Some(format));
+        let loaded = Sprite::from_bytes(&bytes).unwrap();
+        assert_eq!((loaded.width(), loaded.height()), (3., 2.));
+        assert_eq!(pixels(&loaded), pixels(&sprite()), "{format:?}");
+    }
+}
+
+#[test]
+fn ppm_round_trip_drops_alpha() {
+    let bytes = sprite().to_bytes(ImageFormat::Ppm).unwrap();
+    let loaded = Sprite::from_bytes(&bytes).unwrap();
+    assert_eq!(loaded.get_pixel(0., 0.), RED);
+    assert_eq!(loaded.get_pixel(1., 0.), Color::rgb(10, 20, 30));
+    assert_eq!(loaded.get_pixel(0., 1.), BLACK);
+}
+
+#[test]
+fn plain_ppm_with_comments() {
+    let text = "P3\n# a comment\n2 1\n15\n15 0 0  0 15 15\n";
+    let sprite = Sprite::from_bytes(text.as_bytes()).unwrap();
+    assert_eq!(
+        pixels(&sprite),
+        [Color::rgb(255, 0, 0), Color::rgb(0, 255, 255)]
+    );
+}
+
+#[test]
+fn bottom_up_24_bit_bmp() {
+    let mut bytes = b"BM".to_vec();
+    bytes.extend_from_slice(&[0; 8]);
+    bytes.extend_from_slice(&54u32.to_le_bytes());
+    bytes.extend_from_slice(&40u32.to_le_bytes());
+    bytes.extend_from_slice(&1i32.to_le_bytes());
+    bytes.extend_from_slice(&2i32.to_le_bytes());
+    bytes.extend_from_slice(&1u16.to_le_bytes());
+    bytes.extend_from_slice(&24u16.to_le_bytes());
+    bytes.extend_from_slice(&[0; 24]);
+    // rows are padded to 4 bytes, the bottom one comes first
+    bytes.extend_from_slice(&[255, 0, 0, 0, 0, 0, 255, 0]);
+    let sprite = Sprite::from_bytes(&bytes).unwrap();
+    assert_eq!(
+        pixels(&sprite),
+        [Color::rgb(255, 0, 0), Color::rgb(0, 0, 255)]
+    );
+}
+
+#[test]
+fn bitfield_bmp_with_full_width_masks() {
+    let mut bytes = b"BM".to_vec();
+    bytes.extend_from_slice(&[0; 8]);
+    bytes.extend_from_slice(&70u32.to_le_bytes());
+    bytes.extend_from_slice(&56u32.to_le_bytes());
+    bytes.extend_from_slice(&1i32.to_le_bytes());
+    bytes.extend_from_slice(&1i32.to_le_bytes());
+    bytes.extend_from_slice(&1u16.to_le_bytes());
+    bytes.extend_from_slice(&32u16.to_le_bytes());
+    bytes.extend_from_slice(&3u32.to_le_bytes());
+    bytes.extend_from_slice(&[0; 20]);
+    for mask in [u32::MAX, 0xff00, 0xff, u32::MAX] {
+        bytes.extend_from_slice(&mask.to_le_bytes());
+    }
+    bytes.extend_from_slice(&u32::MAX.to_le_bytes());
+    let sprite = Sprite::from_bytes(&bytes).unwrap();
+    assert_eq!(sprite.get_pixel(0., 0.), Color::rgba(255, 255, 255, 255));
+}
+
+#[test]
+fn save_and_load_by_extension() {
+    let path = std::env::temp_dir().join(format!("teremder-{}.png", std::process::id()));
+    sprite().save(&path).unwrap();
+    let loaded = Sprite::load(&path).unwrap();
+    std::fs::remove_file(&path).unwrap();
+    assert_eq!(pixels(&loaded), pixels(&sprite()));
+
+    assert!(matches!(
+        sprite().save("sprite.gif"),
+        Err(ImageError::UnknownFormat)
+    ));
+    assert!(matches!(
+        Sprite::load("does/not/exist.png"),
+        Err(ImageError::Io(_))
+    ));
+}
+
+#[test]
+fn malformed_files_are_errors() {
+    assert!(matches!(
+        Sprite::from_bytes(b"GIF89a"),
+        Err(ImageError::UnknownFormat)
+    ));
+    for format in [ImageFormat::Png, ImageFormat::Bmp, ImageFormat::Ppm] {
+        let bytes = sprite().to_bytes(format).unwrap();
+        for len in [12, bytes.len() / 2, bytes.len() - 13] {
+            let result = Sprite::from_bytes(&bytes[..len]);
+            assert!(
+                matches!(result, Err(ImageError::Malformed(_))),
+                "{format:?} cut at {len}: {:?}",
+                result.map(|_| ())
+            );
+        }
+    }
+    assert!(matches!(
+        Sprite::from_bytes(b"P3 1 1 255 0 300 0"),
+        Err(ImageError::Malformed(_))
+    ));
+    assert!(matches!(
+        Sprite::from_bytes(b"P6 4611686018427387904 1 65535 "),
+        Err(ImageError::Malformed(_))
+    ));
+}
+
+#[test]
+fn sprites_too_large_for_bmp_are_errors() {
+    let wide = Sprite::new(3e9, 0.);
+    assert!(matches!(
+        wide.to_bytes(ImageFormat::Bmp),
+        Err(ImageError::Unsupported(_))
+    ));
+}
+
+#[test]
+fn text_sprites() {
+    let coin = Sprite::from_text(include_str!("sprites/coin.txt")).unwrap();
+    assert_eq!((coin.width(), coin.height()), (5., 5.));
+    assert_eq!(coin.get_pixel(0., 0.), BLANK);
+    assert_eq!(coin.get_pixel(2., 1.), Color::rgba(255, 255, 255, 0xc0));
+    assert_eq!(coin.get_pixel(3., 3.), ORANGE);
+
+    let again = Sprite::from_text(&coin.to_text()).unwrap();
+    assert_eq!(pixels(&again), pixels(&coin));
+
+    let block = sprite! {
+        '.' => BLANK,
+        'r' => RED,
+        'w' => Color::hex(0xffffff);
+        "rrw",
+        "r..",
+    };
+    assert_eq!(pixels(&block)[..3], [RED, RED, WHITE]);
+    assert_eq!(block.get_pixel(2., 1.), BLANK);
+}
+
+#[test]
+fn malformed_text_sprites_are_errors() {
+    let error = |text: &str| match Sprite::from_text(text) {
+        Err(ImageError::Malformed(what)) => what,
+        result => panic!("expected an error, got {:?}", result.map(|_| ())),
+    };
+    assert_eq!(
+        error("a ff00\n---\na"),
+        "line 1: invalid color \"ff00\" for 'a'"
+    );
+    assert_eq!(
+        error("a ff0000\n---\naa\na"),
+        "grid starting at line 3: row 1 is not 2 pixels wide"
+    );
+    assert_eq!(
+        error("a ff0000\n---\nab"),
+        "grid starting at line 3: 'b' in row 0 is not in the legend"
+    );
+}